@@ -0,0 +1,315 @@
+use crate::{Float, Matrix4x4, Point, Ray};
+
+/// Axis-aligned bounding box, may be empty or extend to infinity.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct BoundingBox {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl Default for BoundingBox {
+    fn default() -> Self {
+        BoundingBox::empty()
+    }
+}
+
+impl BoundingBox {
+    pub fn empty() -> Self {
+        BoundingBox {
+            min: Point {
+                x: Float::INFINITY,
+                y: Float::INFINITY,
+                z: Float::INFINITY,
+            },
+            max: Point {
+                x: -Float::INFINITY,
+                y: -Float::INFINITY,
+                z: -Float::INFINITY,
+            },
+        }
+    }
+
+    pub fn infinite() -> Self {
+        BoundingBox {
+            min: BoundingBox::empty().max,
+            max: BoundingBox::empty().min,
+        }
+    }
+
+    /// Smallest box containing all points.
+    pub fn from_points(points: impl IntoIterator<Item = Point>) -> Self {
+        points
+            .into_iter()
+            .fold(BoundingBox::empty(), |bounds, point| {
+                bounds.merge(&BoundingBox {
+                    min: point,
+                    max: point,
+                })
+            })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.min.x > self.max.x || self.min.y > self.max.y || self.min.z > self.max.z
+    }
+
+    /// Checks whether the box is neither empty nor extends to infinity.
+    pub fn is_finite(&self) -> bool {
+        [self.min, self.max]
+            .iter()
+            .all(|p| p.x.is_finite() && p.y.is_finite() && p.z.is_finite())
+    }
+
+    pub fn merge(&self, other: &BoundingBox) -> BoundingBox {
+        BoundingBox {
+            min: Point {
+                x: self.min.x.min(other.min.x),
+                y: self.min.y.min(other.min.y),
+                z: self.min.z.min(other.min.z),
+            },
+            max: Point {
+                x: self.max.x.max(other.max.x),
+                y: self.max.y.max(other.max.y),
+                z: self.max.z.max(other.max.z),
+            },
+        }
+    }
+
+    pub fn contains_point(&self, point: Point) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x)
+            && (self.min.y..=self.max.y).contains(&point.y)
+            && (self.min.z..=self.max.z).contains(&point.z)
+    }
+
+    pub fn contains_box(&self, other: &BoundingBox) -> bool {
+        other.is_empty() || (self.contains_point(other.min) && self.contains_point(other.max))
+    }
+
+    /// Box containing this box after applying the transform.
+    ///
+    /// Boxes extending to infinity stay infinite in all directions.
+    pub fn transform(&self, transform: &Matrix4x4) -> BoundingBox {
+        if self.is_empty() {
+            return *self;
+        }
+        if !self.is_finite() {
+            return BoundingBox::infinite();
+        }
+        let (min, max) = (self.min, self.max);
+        let corners = [min.x, max.x].into_iter().flat_map(|x| {
+            [min.y, max.y]
+                .into_iter()
+                .flat_map(move |y| [min.z, max.z].into_iter().map(move |z| Point { x, y, z }))
+        });
+        BoundingBox::from_points(corners.map(|corner| {
+            transform
+                .matmul(corner)
+                .try_into()
+                .expect("should be convertible to Point")
+        }))
+    }
+
+    /// Checks whether the line through the ray hits the box, ignoring the sign of `t`.
+    pub fn intersects(&self, ray: &Ray) -> bool {
+        let axes = [
+            (self.min.x, self.max.x, ray.origin.x, ray.direction.x),
+            (self.min.y, self.max.y, ray.origin.y, ray.direction.y),
+            (self.min.z, self.max.z, ray.origin.z, ray.direction.z),
+        ];
+        let mut tmin = -Float::INFINITY;
+        let mut tmax = Float::INFINITY;
+        for (min, max, origin, direction) in axes {
+            if direction == 0.0 {
+                if origin < min || origin > max {
+                    return false;
+                }
+                continue;
+            }
+            let t1 = (min - origin) / direction;
+            let t2 = (max - origin) / direction;
+            // `max` and `min` ignore NaN, which occurs for infinite boxes.
+            tmin = tmin.max(t1.min(t2));
+            tmax = tmax.min(t1.max(t2));
+        }
+        tmin <= tmax
+    }
+
+    pub fn surface_area(&self) -> Float {
+        if self.is_empty() {
+            return 0.0;
+        }
+        let size = self.max - self.min;
+        2.0 * (size.x * size.y + size.y * size.z + size.z * size.x)
+    }
+
+    pub fn centroid(&self) -> Point {
+        self.min + (self.max - self.min) / 2.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{approx_eq, Vector};
+
+    fn point(x: Float, y: Float, z: Float) -> Point {
+        Point { x, y, z }
+    }
+
+    fn unit_box() -> BoundingBox {
+        BoundingBox {
+            min: point(-1.0, -1.0, -1.0),
+            max: point(1.0, 1.0, 1.0),
+        }
+    }
+
+    #[test]
+    fn default_box_is_empty() {
+        let bounds = BoundingBox::default();
+        assert!(bounds.is_empty());
+        assert!(!bounds.is_finite());
+        assert_eq!(bounds.surface_area(), 0.0);
+    }
+
+    #[test]
+    fn create_box_from_points() {
+        let bounds = BoundingBox::from_points([point(-5.0, 2.0, 0.0), point(7.0, 0.0, -3.0)]);
+        assert_eq!(bounds.min, point(-5.0, 0.0, -3.0));
+        assert_eq!(bounds.max, point(7.0, 2.0, 0.0));
+        assert!(bounds.is_finite());
+    }
+
+    #[test]
+    fn merge_boxes() {
+        let a = BoundingBox {
+            min: point(-5.0, -2.0, 0.0),
+            max: point(7.0, 4.0, 4.0),
+        };
+        let b = BoundingBox {
+            min: point(8.0, -7.0, -2.0),
+            max: point(14.0, 2.0, 8.0),
+        };
+        let merged = a.merge(&b);
+        assert_eq!(merged.min, point(-5.0, -7.0, -2.0));
+        assert_eq!(merged.max, point(14.0, 4.0, 8.0));
+        assert_eq!(a.merge(&BoundingBox::empty()), a);
+    }
+
+    #[test]
+    fn box_contains_point_and_box() {
+        let bounds = BoundingBox {
+            min: point(5.0, -2.0, 0.0),
+            max: point(11.0, 4.0, 7.0),
+        };
+        assert!(bounds.contains_point(point(5.0, -2.0, 0.0)));
+        assert!(bounds.contains_point(point(8.0, 1.0, 3.0)));
+        assert!(!bounds.contains_point(point(3.0, 0.0, 3.0)));
+        assert!(!bounds.contains_point(point(8.0, 1.0, 8.0)));
+        assert!(bounds.contains_box(&BoundingBox {
+            min: point(6.0, -1.0, 1.0),
+            max: point(10.0, 3.0, 6.0),
+        }));
+        assert!(!bounds.contains_box(&BoundingBox {
+            min: point(4.0, -3.0, -1.0),
+            max: point(10.0, 3.0, 6.0),
+        }));
+    }
+
+    #[test]
+    fn transform_box() {
+        let transform = Matrix4x4::rotation_x(std::f64::consts::FRAC_PI_4 as Float)
+            .matmul(Matrix4x4::rotation_y(std::f64::consts::FRAC_PI_4 as Float));
+        let bounds = unit_box().transform(&transform);
+        let sqrt2 = std::f64::consts::SQRT_2 as Float;
+        let expected = [sqrt2, 1.0 + sqrt2 / 2.0, 1.0 + sqrt2 / 2.0];
+        for (value, expected) in [bounds.max.x, bounds.max.y, bounds.max.z]
+            .iter()
+            .zip(expected)
+        {
+            assert!(approx_eq!(*value, expected, atol = 1e-5));
+        }
+        assert_eq!(
+            bounds.min,
+            point(-bounds.max.x, -bounds.max.y, -bounds.max.z)
+        );
+    }
+
+    #[test]
+    fn transform_infinite_and_empty_box() {
+        let transform = Matrix4x4::translation(1.0, 2.0, 3.0);
+        let plane = BoundingBox {
+            min: point(-Float::INFINITY, 0.0, -Float::INFINITY),
+            max: point(Float::INFINITY, 0.0, Float::INFINITY),
+        };
+        assert_eq!(plane.transform(&transform), BoundingBox::infinite());
+        assert!(BoundingBox::empty().transform(&transform).is_empty());
+    }
+
+    #[test]
+    fn ray_intersects_box() {
+        let bounds = BoundingBox {
+            min: point(5.0, -2.0, 0.0),
+            max: point(11.0, 4.0, 7.0),
+        };
+        let cases = [
+            (point(15.0, 1.0, 2.0), (-1.0, 0.0, 0.0), true),
+            (point(-5.0, -1.0, 4.0), (1.0, 0.0, 0.0), true),
+            (point(7.0, 6.0, 5.0), (0.0, -1.0, 0.0), true),
+            (point(9.0, -5.0, 6.0), (0.0, 1.0, 0.0), true),
+            (point(8.0, 2.0, 12.0), (0.0, 0.0, -1.0), true),
+            (point(6.0, 0.0, -5.0), (0.0, 0.0, 1.0), true),
+            (point(8.0, 1.0, 3.5), (0.0, 0.0, 1.0), true),
+            // Behind the origin, which still counts.
+            (point(20.0, 1.0, 2.0), (1.0, 0.0, 0.0), true),
+            (point(9.0, -1.0, -8.0), (2.0, 4.0, 6.0), false),
+            (point(8.0, 3.0, -4.0), (6.0, 2.0, 4.0), false),
+            (point(9.0, -1.0, -2.0), (4.0, 6.0, 2.0), false),
+            (point(4.0, 0.0, 9.0), (0.0, 0.0, -1.0), false),
+            (point(8.0, 6.0, -1.0), (0.0, -1.0, 0.0), false),
+            (point(12.0, 5.0, 4.0), (-1.0, 0.0, 0.0), false),
+        ];
+        for (origin, (x, y, z), expected) in cases {
+            let ray = Ray {
+                origin,
+                direction: Vector { x, y, z }.normalize(),
+            };
+            assert_eq!(bounds.intersects(&ray), expected, "{:?}", ray);
+        }
+    }
+
+    #[test]
+    fn ray_intersects_infinite_box() {
+        let plane = BoundingBox {
+            min: point(-Float::INFINITY, 0.0, -Float::INFINITY),
+            max: point(Float::INFINITY, 0.0, Float::INFINITY),
+        };
+        let ray = |y: Float, direction: Vector| Ray {
+            origin: point(0.0, y, 0.0),
+            direction,
+        };
+        let down = Vector {
+            x: 0.0,
+            y: -1.0,
+            z: 0.0,
+        };
+        let forward = Vector {
+            x: 0.0,
+            y: 0.0,
+            z: 1.0,
+        };
+        assert!(plane.intersects(&ray(1.0, down)));
+        assert!(!plane.intersects(&ray(1.0, forward)));
+        assert!(plane.intersects(&ray(0.0, forward)));
+        assert!(BoundingBox::infinite().intersects(&ray(1.0, forward)));
+        assert!(!BoundingBox::empty().intersects(&ray(1.0, forward)));
+    }
+
+    #[test]
+    fn surface_area_and_centroid() {
+        let bounds = BoundingBox {
+            min: point(0.0, 0.0, 0.0),
+            max: point(1.0, 2.0, 3.0),
+        };
+        assert_eq!(bounds.surface_area(), 22.0);
+        assert_eq!(bounds.centroid(), point(0.5, 1.0, 1.5));
+    }
+}
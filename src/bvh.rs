@@ -0,0 +1,256 @@
+use crate::{BoundingBox, Float, Ray};
+
+// Cost of visiting a node relative to intersecting a single item.
+const TRAVERSAL_COST: Float = 0.125;
+const MAX_LEAF_SIZE: usize = 8;
+
+#[derive(Debug, Clone)]
+enum Node {
+    Leaf {
+        bounds: BoundingBox,
+        start: usize,
+        end: usize,
+    },
+    Interior {
+        bounds: BoundingBox,
+        left: usize,
+        right: usize,
+    },
+}
+
+/// Bounding volume hierarchy over a list of bounding boxes, built using the
+/// surface area heuristic.
+///
+/// Items are referred to by their index. Items with unbounded (or empty) boxes,
+/// like planes, are not part of the tree and always visited.
+#[derive(Debug, Clone, Default)]
+pub struct Bvh {
+    nodes: Vec<Node>,
+    indices: Vec<usize>,
+    unbounded: Vec<usize>,
+}
+
+impl Bvh {
+    pub fn new(bounds: &[BoundingBox]) -> Self {
+        let (mut indices, unbounded): (Vec<usize>, Vec<usize>) =
+            (0..bounds.len()).partition(|&i| bounds[i].is_finite());
+        let mut bvh = Bvh {
+            nodes: vec![],
+            indices: vec![],
+            unbounded,
+        };
+        if !indices.is_empty() {
+            let len = indices.len();
+            bvh.build(bounds, &mut indices, 0, len);
+        }
+        bvh.indices = indices;
+        bvh
+    }
+
+    /// Calls `visit` with the index of every item whose bounding box is hit by the
+    /// line through the ray (including the part behind its origin).
+    pub fn traverse(&self, ray: &Ray, mut visit: impl FnMut(usize)) {
+        self.unbounded.iter().for_each(|&i| visit(i));
+        let mut stack = if self.nodes.is_empty() {
+            vec![]
+        } else {
+            vec![0]
+        };
+        while let Some(node) = stack.pop() {
+            match &self.nodes[node] {
+                Node::Leaf { bounds, start, end } => {
+                    if bounds.intersects(ray) {
+                        self.indices[*start..*end].iter().for_each(|&i| visit(i));
+                    }
+                }
+                Node::Interior {
+                    bounds,
+                    left,
+                    right,
+                } => {
+                    if bounds.intersects(ray) {
+                        stack.push(*right);
+                        stack.push(*left);
+                    }
+                }
+            }
+        }
+    }
+
+    // Builds the subtree for `indices[start..end]`, returns the index of its root.
+    fn build(
+        &mut self,
+        bounds: &[BoundingBox],
+        indices: &mut [usize],
+        start: usize,
+        end: usize,
+    ) -> usize {
+        let items = &mut indices[start..end];
+        let node_bounds = items
+            .iter()
+            .fold(BoundingBox::empty(), |b, &i| b.merge(&bounds[i]));
+        let node = self.nodes.len();
+        self.nodes.push(Node::Leaf {
+            bounds: node_bounds,
+            start,
+            end,
+        });
+        if items.len() == 1 {
+            return node;
+        }
+
+        let (axis, split, cost) = best_split(bounds, items);
+        let leaf_cost = items.len() as Float * node_bounds.surface_area();
+        if cost >= leaf_cost && items.len() <= MAX_LEAF_SIZE {
+            return node;
+        }
+        // Fall back to the median if the surface area heuristic cannot decide,
+        // e.g. if all boxes are degenerate.
+        let split = if cost.is_finite() && cost < leaf_cost {
+            split
+        } else {
+            items.len() / 2
+        };
+        sort_by_centroid(bounds, items, axis);
+        let left = self.build(bounds, indices, start, start + split);
+        let right = self.build(bounds, indices, start + split, end);
+        self.nodes[node] = Node::Interior {
+            bounds: node_bounds,
+            left,
+            right,
+        };
+        node
+    }
+}
+
+fn centroid(bounds: &BoundingBox, axis: usize) -> Float {
+    let centroid = bounds.centroid();
+    [centroid.x, centroid.y, centroid.z][axis]
+}
+
+fn sort_by_centroid(bounds: &[BoundingBox], items: &mut [usize], axis: usize) {
+    items.sort_by(|&a, &b| centroid(&bounds[a], axis).total_cmp(&centroid(&bounds[b], axis)));
+}
+
+// Returns the axis, the number of items on the left and the cost of the cheapest
+// split, with the cost being the surface areas weighted by the number of items.
+fn best_split(bounds: &[BoundingBox], items: &mut [usize]) -> (usize, usize, Float) {
+    let mut best = (0, items.len() / 2, Float::INFINITY);
+    for axis in 0..3 {
+        sort_by_centroid(bounds, items, axis);
+        // Surface areas of all suffixes, i.e. the right sides of the splits.
+        let mut right_areas = vec![0.0; items.len()];
+        let mut right = BoundingBox::empty();
+        for (i, &item) in items.iter().enumerate().rev() {
+            right = right.merge(&bounds[item]);
+            right_areas[i] = right.surface_area();
+        }
+        let mut left = BoundingBox::empty();
+        for split in 1..items.len() {
+            left = left.merge(&bounds[items[split - 1]]);
+            let cost = TRAVERSAL_COST * left.merge(&right).surface_area()
+                + split as Float * left.surface_area()
+                + (items.len() - split) as Float * right_areas[split];
+            if cost < best.2 {
+                best = (axis, split, cost);
+            }
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Point, Vector};
+
+    fn cube_at(x: Float, y: Float, z: Float) -> BoundingBox {
+        BoundingBox {
+            min: Point {
+                x: x - 0.5,
+                y: y - 0.5,
+                z: z - 0.5,
+            },
+            max: Point {
+                x: x + 0.5,
+                y: y + 0.5,
+                z: z + 0.5,
+            },
+        }
+    }
+
+    fn visited(bvh: &Bvh, ray: &Ray) -> Vec<usize> {
+        let mut indices = vec![];
+        bvh.traverse(ray, |i| indices.push(i));
+        indices.sort();
+        indices
+    }
+
+    fn ray_along_z(x: Float, y: Float) -> Ray {
+        Ray {
+            origin: Point { x, y, z: -10.0 },
+            direction: Vector {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0,
+            },
+        }
+    }
+
+    #[test]
+    fn empty_bvh_visits_nothing() {
+        let bvh = Bvh::new(&[]);
+        assert!(visited(&bvh, &ray_along_z(0.0, 0.0)).is_empty());
+    }
+
+    #[test]
+    fn traversal_visits_only_boxes_hit_by_line() {
+        // Grid of 10x10 unit cubes in the xy plane, two of them stacked along z.
+        let mut bounds: Vec<BoundingBox> = (0..100)
+            .map(|i| cube_at(2.0 * (i % 10) as Float, 2.0 * (i / 10) as Float, 0.0))
+            .collect();
+        bounds.push(cube_at(4.0, 6.0, 5.0));
+        let bvh = Bvh::new(&bounds);
+        assert_eq!(visited(&bvh, &ray_along_z(4.0, 6.0)), vec![32, 100]);
+        assert_eq!(visited(&bvh, &ray_along_z(18.2, 0.1)), vec![9]);
+        assert!(visited(&bvh, &ray_along_z(1.0, 1.0)).is_empty());
+        // The tree actually prunes, i.e. it is not a single leaf.
+        assert!(bvh.nodes.len() > 1);
+    }
+
+    #[test]
+    fn unbounded_items_are_always_visited() {
+        let bounds = [
+            cube_at(0.0, 0.0, 0.0),
+            BoundingBox::infinite(),
+            cube_at(5.0, 0.0, 0.0),
+            BoundingBox::empty(),
+        ];
+        let bvh = Bvh::new(&bounds);
+        assert_eq!(visited(&bvh, &ray_along_z(5.0, 0.0)), vec![1, 2, 3]);
+        assert_eq!(visited(&bvh, &ray_along_z(2.5, 0.0)), vec![1, 3]);
+    }
+
+    #[test]
+    fn surface_area_heuristic_separates_clusters() {
+        // Two distant clusters, which should end up in different subtrees.
+        let bounds: Vec<BoundingBox> = (0..20)
+            .map(|i| {
+                let offset = if i % 2 == 0 { 0.0 } else { 100.0 };
+                cube_at(offset + (i / 2) as Float * 0.1, 0.0, 0.0)
+            })
+            .collect();
+        let bvh = Bvh::new(&bounds);
+        match &bvh.nodes[0] {
+            Node::Interior { left, right, .. } => {
+                for child in [left, right] {
+                    let bounds = match &bvh.nodes[*child] {
+                        Node::Leaf { bounds, .. } | Node::Interior { bounds, .. } => bounds,
+                    };
+                    assert!(bounds.max.x - bounds.min.x < 2.0);
+                }
+            }
+            Node::Leaf { .. } => panic!("root should be split"),
+        }
+    }
+}
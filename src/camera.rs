@@ -0,0 +1,292 @@
+use crate::{Canvas, Color, Float, Point, Ray, Transform, Vector, World};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+// Edge length of the square tiles that are distributed between render threads.
+const TILE_SIZE: usize = 16;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Camera {
+    pub transform: Transform,
+    hsize: usize,
+    vsize: usize,
+    field_of_view: Float,
+    half_width: Float,
+    half_height: Float,
+    pixel_size: Float,
+}
+
+impl Camera {
+    pub fn new(hsize: usize, vsize: usize, field_of_view: Float) -> Self {
+        let half_view = Float::tan(field_of_view / 2.0);
+        let aspect = hsize as Float / vsize as Float;
+        let (half_width, half_height) = if aspect >= 1.0 {
+            (half_view, half_view / aspect)
+        } else {
+            (half_view * aspect, half_view)
+        };
+        Camera {
+            transform: Transform::identity(),
+            hsize,
+            vsize,
+            field_of_view,
+            half_width,
+            half_height,
+            pixel_size: half_width * 2.0 / hsize as Float,
+        }
+    }
+
+    pub fn hsize(&self) -> usize {
+        self.hsize
+    }
+
+    pub fn vsize(&self) -> usize {
+        self.vsize
+    }
+
+    pub fn field_of_view(&self) -> Float {
+        self.field_of_view
+    }
+
+    pub fn pixel_size(&self) -> Float {
+        self.pixel_size
+    }
+
+    /// Returns the ray that starts at the camera and passes through the center of
+    /// the given pixel.
+    pub fn ray_for_pixel(&self, x: usize, y: usize) -> Ray {
+        // Offset from the edge of the canvas to the pixel's center.
+        let x_offset = (x as Float + 0.5) * self.pixel_size;
+        let y_offset = (y as Float + 0.5) * self.pixel_size;
+        // The camera looks toward -z, so +x is to the left.
+        let world_x = self.half_width - x_offset;
+        let world_y = self.half_height - y_offset;
+
+        let inv_transform = self.transform.inverse();
+        let pixel: Point = inv_transform
+            .matmul(Point {
+                x: world_x,
+                y: world_y,
+                z: -1.0,
+            })
+            .try_into()
+            .expect("should be convertible to Point");
+        let origin: Point = inv_transform
+            .matmul(Point::origin())
+            .try_into()
+            .expect("should be convertible to Point");
+        let direction: Vector = (pixel - origin).normalize();
+        Ray { origin, direction }
+    }
+
+    /// Renders the world as seen from the camera.
+    pub fn render(&self, world: &World) -> Canvas {
+        let mut canvas = Canvas::new(self.hsize, self.vsize);
+        for y in 0..self.vsize {
+            for x in 0..self.hsize {
+                let ray = self.ray_for_pixel(x, y);
+                canvas
+                    .set_pixel(x, y, world.color_at(&ray))
+                    .expect("pixel should be inside the canvas");
+            }
+        }
+        canvas
+    }
+
+    /// Renders the world using all available CPU threads.
+    pub fn render_parallel(&self, world: &World) -> Canvas {
+        let threads = thread::available_parallelism().map_or(1, |n| n.get());
+        self.render_with_threads(world, threads)
+    }
+
+    /// Renders the world by splitting the image into tiles that are picked up by
+    /// `threads` worker threads. The result is identical to `render`.
+    pub fn render_with_threads(&self, world: &World, threads: usize) -> Canvas {
+        let tiles_x = self.hsize.div_ceil(TILE_SIZE);
+        let tiles_y = self.vsize.div_ceil(TILE_SIZE);
+        let next_tile = AtomicUsize::new(0);
+        let canvas = Mutex::new(Canvas::new(self.hsize, self.vsize));
+        thread::scope(|scope| {
+            for _ in 0..threads.max(1) {
+                scope.spawn(|| loop {
+                    let tile = next_tile.fetch_add(1, Ordering::Relaxed);
+                    if tile >= tiles_x * tiles_y {
+                        break;
+                    }
+                    let x0 = (tile % tiles_x) * TILE_SIZE;
+                    let y0 = (tile / tiles_x) * TILE_SIZE;
+                    let x_range = x0..(x0 + TILE_SIZE).min(self.hsize);
+                    let y_range = y0..(y0 + TILE_SIZE).min(self.vsize);
+                    // Render without holding the lock, then copy the tile over.
+                    let colors: Vec<Color> = y_range
+                        .clone()
+                        .flat_map(|y| x_range.clone().map(move |x| (x, y)))
+                        .map(|(x, y)| world.color_at(&self.ray_for_pixel(x, y)))
+                        .collect();
+                    let mut canvas = canvas.lock().expect("canvas lock should not be poisoned");
+                    let pixels = y_range.flat_map(|y| x_range.clone().map(move |x| (x, y)));
+                    for ((x, y), color) in pixels.zip(colors) {
+                        canvas
+                            .set_pixel(x, y, color)
+                            .expect("pixel should be inside the canvas");
+                    }
+                });
+            }
+        });
+        canvas
+            .into_inner()
+            .expect("canvas lock should not be poisoned")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::tests::default_world;
+    use crate::{approx_eq, Color, Matrix4x4};
+    use std::f64::consts::{FRAC_1_SQRT_2, FRAC_PI_2, FRAC_PI_4};
+
+    macro_rules! assert_tuple_approx_eq {
+        ($tuple1:expr, $tuple2:expr) => {
+            assert!(approx_eq!($tuple1.x, $tuple2.x));
+            assert!(approx_eq!($tuple1.y, $tuple2.y));
+            assert!(approx_eq!($tuple1.z, $tuple2.z));
+        };
+    }
+
+    #[test]
+    fn create_camera() {
+        let camera = Camera::new(160, 120, FRAC_PI_2 as Float);
+        assert_eq!(camera.hsize(), 160);
+        assert_eq!(camera.vsize(), 120);
+        assert_eq!(camera.field_of_view(), FRAC_PI_2 as Float);
+        assert_eq!(camera.transform, Transform::identity());
+    }
+
+    #[test]
+    fn pixel_size_for_horizontal_canvas() {
+        let camera = Camera::new(200, 125, FRAC_PI_2 as Float);
+        assert!(approx_eq!(camera.pixel_size(), 0.01));
+    }
+
+    #[test]
+    fn pixel_size_for_vertical_canvas() {
+        let camera = Camera::new(125, 200, FRAC_PI_2 as Float);
+        assert!(approx_eq!(camera.pixel_size(), 0.01));
+    }
+
+    #[test]
+    fn ray_through_center_of_canvas() {
+        let camera = Camera::new(201, 101, FRAC_PI_2 as Float);
+        let ray = camera.ray_for_pixel(100, 50);
+        assert_tuple_approx_eq!(ray.origin, Point::origin());
+        assert_tuple_approx_eq!(
+            ray.direction,
+            Vector {
+                x: 0.0,
+                y: 0.0,
+                z: -1.0
+            }
+        );
+    }
+
+    #[test]
+    fn ray_through_corner_of_canvas() {
+        let camera = Camera::new(201, 101, FRAC_PI_2 as Float);
+        let ray = camera.ray_for_pixel(0, 0);
+        assert_tuple_approx_eq!(ray.origin, Point::origin());
+        assert!(approx_eq!(ray.direction.x, 0.66519, atol = 1e-5));
+        assert!(approx_eq!(ray.direction.y, 0.33259, atol = 1e-5));
+        assert!(approx_eq!(ray.direction.z, -0.66851, atol = 1e-5));
+    }
+
+    #[test]
+    fn ray_when_camera_is_transformed() {
+        let mut camera = Camera::new(201, 101, FRAC_PI_2 as Float);
+        camera.transform = Transform::new(
+            Matrix4x4::rotation_y(FRAC_PI_4 as Float)
+                .matmul(Matrix4x4::translation(0.0, -2.0, 5.0)),
+        )
+        .unwrap();
+        let ray = camera.ray_for_pixel(100, 50);
+        assert_tuple_approx_eq!(
+            ray.origin,
+            Point {
+                x: 0.0,
+                y: 2.0,
+                z: -5.0
+            }
+        );
+        assert_tuple_approx_eq!(
+            ray.direction,
+            Vector {
+                x: FRAC_1_SQRT_2 as Float,
+                y: 0.0,
+                z: -FRAC_1_SQRT_2 as Float
+            }
+        );
+    }
+
+    #[test]
+    fn render_world_with_camera() {
+        let world = default_world();
+        let mut camera = Camera::new(11, 11, FRAC_PI_2 as Float);
+        let from = Point {
+            x: 0.0,
+            y: 0.0,
+            z: -5.0,
+        };
+        let to = Point::origin();
+        let up = Vector {
+            x: 0.0,
+            y: 1.0,
+            z: 0.0,
+        };
+        camera.transform = Transform::new(Matrix4x4::view_transform(from, to, up)).unwrap();
+        let image = camera.render(&world);
+        let pixel = image.get_pixel(5, 5).unwrap();
+        let expected = Color {
+            r: 0.38066,
+            g: 0.47583,
+            b: 0.2855,
+        };
+        assert!(approx_eq!(pixel.r, expected.r, atol = 1e-5));
+        assert!(approx_eq!(pixel.g, expected.g, atol = 1e-5));
+        assert!(approx_eq!(pixel.b, expected.b, atol = 1e-5));
+    }
+
+    #[test]
+    fn rendered_canvas_has_camera_size() {
+        let camera = Camera::new(16, 9, FRAC_PI_2 as Float);
+        let image = camera.render(&World::default());
+        assert_eq!(image.width(), 16);
+        assert_eq!(image.height(), 9);
+    }
+
+    #[test]
+    fn parallel_rendering_matches_single_threaded_rendering() {
+        let world = default_world();
+        // Size is not a multiple of the tile size on purpose.
+        let mut camera = Camera::new(37, 23, FRAC_PI_2 as Float);
+        camera.transform = Transform::new(Matrix4x4::view_transform(
+            Point {
+                x: 0.0,
+                y: 0.5,
+                z: -4.0,
+            },
+            Point::origin(),
+            Vector {
+                x: 0.0,
+                y: 1.0,
+                z: 0.0,
+            },
+        ))
+        .unwrap();
+        let expected = camera.render(&world);
+        assert_eq!(camera.render_with_threads(&world, 1), expected);
+        assert_eq!(camera.render_with_threads(&world, 4), expected);
+        assert_eq!(camera.render_with_threads(&world, 100), expected);
+        assert_eq!(camera.render_parallel(&world), expected);
+    }
+}
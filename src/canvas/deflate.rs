@@ -0,0 +1,359 @@
+//! Minimal zlib (RFC 1950) compressor, using LZ77 and the fixed Huffman code of
+//! deflate (RFC 1951).
+
+const WINDOW_SIZE: usize = 32768;
+const MIN_MATCH: usize = 3;
+const MAX_MATCH: usize = 258;
+// Number of earlier positions tried per match, trades speed for compression.
+const MAX_CHAIN: usize = 64;
+const HASH_BITS: u32 = 15;
+const NONE: usize = usize::MAX;
+const END_OF_BLOCK: u16 = 256;
+
+pub(super) const LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+pub(super) const LENGTH_EXTRA_BITS: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+pub(super) const DISTANCE_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+pub(super) const DISTANCE_EXTRA_BITS: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+
+/// Compresses the data into a zlib stream consisting of a single deflate block.
+pub(super) fn zlib_compress(data: &[u8]) -> Vec<u8> {
+    let mut writer = BitWriter::default();
+    // Deflate with 32K window, default compression level, no preset dictionary.
+    writer.write_bits(0x78, 8);
+    writer.write_bits(0x9C, 8);
+    // Single final block using the fixed Huffman code.
+    writer.write_bits(1, 1);
+    writer.write_bits(1, 2);
+
+    let mut matcher = MatchFinder::new(data);
+    let mut pos = 0;
+    while pos < data.len() {
+        let (length, distance) = matcher.longest_match(pos);
+        if length >= MIN_MATCH {
+            writer.write_match(length, distance);
+            for p in pos..pos + length {
+                matcher.insert(p);
+            }
+            pos += length;
+        } else {
+            writer.write_symbol(data[pos] as u16);
+            matcher.insert(pos);
+            pos += 1;
+        }
+    }
+    writer.write_symbol(END_OF_BLOCK);
+
+    let mut bytes = writer.finish();
+    bytes.extend(adler32(data).to_be_bytes());
+    bytes
+}
+
+pub(super) fn adler32(data: &[u8]) -> u32 {
+    const MOD: u32 = 65521;
+    let (mut a, mut b) = (1u32, 0u32);
+    // Sums can be accumulated for a while before they have to be reduced.
+    for chunk in data.chunks(4096) {
+        for &byte in chunk {
+            a += byte as u32;
+            b += a;
+        }
+        a %= MOD;
+        b %= MOD;
+    }
+    (b << 16) | a
+}
+
+// Finds earlier occurrences of data using hash chains over the last `WINDOW_SIZE` bytes.
+struct MatchFinder<'a> {
+    data: &'a [u8],
+    // Most recent position for each hash value.
+    head: Vec<usize>,
+    // Previous position with the same hash, indexed by position modulo window size.
+    prev: Vec<usize>,
+}
+
+impl<'a> MatchFinder<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        MatchFinder {
+            data,
+            head: vec![NONE; 1 << HASH_BITS],
+            prev: vec![NONE; WINDOW_SIZE],
+        }
+    }
+
+    fn hash(&self, pos: usize) -> usize {
+        let bytes = &self.data[pos..pos + MIN_MATCH];
+        let value = (bytes[0] as u32) << 16 | (bytes[1] as u32) << 8 | bytes[2] as u32;
+        (value.wrapping_mul(2654435761) >> (32 - HASH_BITS)) as usize
+    }
+
+    fn insert(&mut self, pos: usize) {
+        if pos + MIN_MATCH <= self.data.len() {
+            let hash = self.hash(pos);
+            self.prev[pos % WINDOW_SIZE] = self.head[hash];
+            self.head[hash] = pos;
+        }
+    }
+
+    // Returns length and distance of the longest earlier match for the data at `pos`.
+    fn longest_match(&self, pos: usize) -> (usize, usize) {
+        if pos + MIN_MATCH > self.data.len() {
+            return (0, 0);
+        }
+        let max_length = MAX_MATCH.min(self.data.len() - pos);
+        let (mut best_length, mut best_distance) = (0, 0);
+        let mut candidate = self.head[self.hash(pos)];
+        for _ in 0..MAX_CHAIN {
+            if candidate == NONE || pos - candidate > WINDOW_SIZE {
+                break;
+            }
+            let length = self.data[candidate..]
+                .iter()
+                .zip(&self.data[pos..pos + max_length])
+                .take_while(|(a, b)| a == b)
+                .count();
+            if length > best_length {
+                (best_length, best_distance) = (length, pos - candidate);
+                if length == max_length {
+                    break;
+                }
+            }
+            let next = self.prev[candidate % WINDOW_SIZE];
+            // The chain is only valid as long as it runs backwards, older entries of
+            // the ring buffer get overwritten.
+            if next == NONE || next >= candidate {
+                break;
+            }
+            candidate = next;
+        }
+        (best_length, best_distance)
+    }
+}
+
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u32,
+    count: u32,
+}
+
+impl BitWriter {
+    // Writes the lowest `count` bits of `value`, least significant bit first.
+    fn write_bits(&mut self, value: u32, count: u32) {
+        self.buffer |= value << self.count;
+        self.count += count;
+        while self.count >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.count -= 8;
+        }
+    }
+
+    // Huffman codes are packed starting with their most significant bit.
+    fn write_code(&mut self, code: u32, length: u32) {
+        self.write_bits(code.reverse_bits() >> (32 - length), length);
+    }
+
+    // Writes a literal/length symbol using the fixed Huffman code.
+    fn write_symbol(&mut self, symbol: u16) {
+        let symbol = symbol as u32;
+        match symbol {
+            0..=143 => self.write_code(0x30 + symbol, 8),
+            144..=255 => self.write_code(0x190 + symbol - 144, 9),
+            256..=279 => self.write_code(symbol - 256, 7),
+            _ => self.write_code(0xC0 + symbol - 280, 8),
+        }
+    }
+
+    fn write_match(&mut self, length: usize, distance: usize) {
+        let index = LENGTH_BASE
+            .iter()
+            .rposition(|&base| base as usize <= length)
+            .expect("match length should be at least 3");
+        self.write_symbol(257 + index as u16);
+        self.write_bits(
+            (length - LENGTH_BASE[index] as usize) as u32,
+            LENGTH_EXTRA_BITS[index] as u32,
+        );
+        let index = DISTANCE_BASE
+            .iter()
+            .rposition(|&base| base as usize <= distance)
+            .expect("match distance should be at least 1");
+        self.write_code(index as u32, 5);
+        self.write_bits(
+            (distance - DISTANCE_BASE[index] as usize) as u32,
+            DISTANCE_EXTRA_BITS[index] as u32,
+        );
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.count > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+        self.bytes
+    }
+}
+
+/// Decompresses a zlib stream, only used to verify the output of the encoders.
+///
+/// Supports stored and fixed Huffman blocks, which is all that `zlib_compress` emits.
+#[cfg(test)]
+pub(super) fn zlib_decompress(data: &[u8]) -> Result<Vec<u8>, String> {
+    struct BitReader<'a> {
+        data: &'a [u8],
+        pos: usize,
+    }
+
+    impl BitReader<'_> {
+        fn bits(&mut self, count: u32) -> Result<u32, String> {
+            let mut value = 0;
+            for i in 0..count {
+                let byte = self
+                    .data
+                    .get(self.pos / 8)
+                    .ok_or("unexpected end of data")?;
+                value |= ((byte >> (self.pos % 8)) as u32 & 1) << i;
+                self.pos += 1;
+            }
+            Ok(value)
+        }
+
+        fn code(&mut self, length: u32) -> Result<u32, String> {
+            let mut value = 0;
+            for _ in 0..length {
+                value = (value << 1) | self.bits(1)?;
+            }
+            Ok(value)
+        }
+
+        fn symbol(&mut self) -> Result<u16, String> {
+            let code = self.code(7)?;
+            if code <= 23 {
+                return Ok(256 + code as u16);
+            }
+            let code = (code << 1) | self.bits(1)?;
+            match code {
+                0x30..=0xBF => Ok((code - 0x30) as u16),
+                0xC0..=0xC7 => Ok((code - 0xC0 + 280) as u16),
+                _ => Ok(((code << 1 | self.bits(1)?) - 0x190 + 144) as u16),
+            }
+        }
+    }
+
+    if data.len() < 6
+        || !(data[0] as u32 * 256 + data[1] as u32).is_multiple_of(31)
+        || data[0] & 0x0F != 8
+    {
+        return Err(String::from("invalid zlib header"));
+    }
+    let mut reader = BitReader { data, pos: 16 };
+    let mut out: Vec<u8> = Vec::new();
+    loop {
+        let last = reader.bits(1)? == 1;
+        match reader.bits(2)? {
+            0 => {
+                reader.pos = reader.pos.div_ceil(8) * 8;
+                let length = reader.bits(16)? as usize;
+                let inverted = reader.bits(16)? as usize;
+                if length != !inverted & 0xFFFF {
+                    return Err(String::from("invalid stored block length"));
+                }
+                for _ in 0..length {
+                    out.push(reader.bits(8)? as u8);
+                }
+            }
+            1 => loop {
+                let symbol = reader.symbol()?;
+                match symbol {
+                    0..=255 => out.push(symbol as u8),
+                    END_OF_BLOCK => break,
+                    _ => {
+                        let index = (symbol - 257) as usize;
+                        let length = LENGTH_BASE[index] as usize
+                            + reader.bits(LENGTH_EXTRA_BITS[index] as u32)? as usize;
+                        let index = reader.code(5)? as usize;
+                        let distance = DISTANCE_BASE[index] as usize
+                            + reader.bits(DISTANCE_EXTRA_BITS[index] as u32)? as usize;
+                        if distance > out.len() {
+                            return Err(String::from("distance too far back"));
+                        }
+                        for _ in 0..length {
+                            out.push(out[out.len() - distance]);
+                        }
+                    }
+                }
+            },
+            _ => return Err(String::from("unsupported block type")),
+        }
+        if last {
+            break;
+        }
+    }
+    let checksum_pos = reader.pos.div_ceil(8);
+    let checksum = data
+        .get(checksum_pos..checksum_pos + 4)
+        .ok_or("missing checksum")?;
+    if u32::from_be_bytes(checksum.try_into().unwrap()) != adler32(&out) {
+        return Err(String::from("checksum mismatch"));
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adler32_of_known_strings() {
+        assert_eq!(adler32(b""), 1);
+        assert_eq!(adler32(b"Wikipedia"), 0x11E60398);
+        assert_eq!(adler32(&[255; 100000]), 0x149A_302C);
+    }
+
+    #[test]
+    fn compress_and_decompress_empty_data() {
+        let compressed = zlib_compress(&[]);
+        assert_eq!(zlib_decompress(&compressed).unwrap(), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn compress_and_decompress_text() {
+        let data = b"Lorem ipsum dolor sit amet, lorem ipsum dolor sit amet, lorem ipsum.";
+        let compressed = zlib_compress(data);
+        assert_eq!(zlib_decompress(&compressed).unwrap(), data);
+    }
+
+    #[test]
+    fn repetitive_data_is_compressed() {
+        let data: Vec<u8> = (0..100000).map(|i| (i % 7) as u8).collect();
+        let compressed = zlib_compress(&data);
+        assert!(compressed.len() < data.len() / 50);
+        assert_eq!(zlib_decompress(&compressed).unwrap(), data);
+    }
+
+    #[test]
+    fn matches_are_limited_to_window() {
+        // Pseudo-random data with repetitions further apart than the window size.
+        let mut state: u32 = 12345;
+        let block: Vec<u8> = (0..40000)
+            .map(|_| {
+                state = state.wrapping_mul(1103515245).wrapping_add(12345);
+                (state >> 16) as u8
+            })
+            .collect();
+        let data = [block.as_slice(), block.as_slice(), &block[..5000]].concat();
+        let compressed = zlib_compress(&data);
+        assert_eq!(zlib_decompress(&compressed).unwrap(), data);
+    }
+}
@@ -0,0 +1,288 @@
+use super::Canvas;
+use crate::{Color, Float};
+
+/// Maps linear colors of arbitrary intensity to the displayable range [0, 1].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ToneMapping {
+    Clamp,
+    Reinhard,
+    /// Reinhard operator that maps `white` (and everything brighter) to 1.
+    ExtendedReinhard {
+        white: Float,
+    },
+    /// Krzysztof Narkowicz's fit of the ACES filmic curve.
+    AcesFilmic,
+    Exposure {
+        exposure: Float,
+    },
+}
+
+impl ToneMapping {
+    pub fn apply(&self, color: Color) -> Color {
+        let map = |c: Float| -> Float {
+            let c = c.max(0.0);
+            let mapped = match *self {
+                ToneMapping::Clamp => c,
+                ToneMapping::Reinhard => c / (1.0 + c),
+                ToneMapping::ExtendedReinhard { white } => {
+                    c * (1.0 + c / (white * white)) / (1.0 + c)
+                }
+                ToneMapping::AcesFilmic => (c * (2.51 * c + 0.03)) / (c * (2.43 * c + 0.59) + 0.14),
+                ToneMapping::Exposure { exposure } => 1.0 - Float::exp(-c * exposure),
+            };
+            mapped.clamp(0.0, 1.0)
+        };
+        Color {
+            r: map(color.r),
+            g: map(color.g),
+            b: map(color.b),
+        }
+    }
+}
+
+/// Transfer function between linear color values and the values stored in a file.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TransferFunction {
+    Linear,
+    Srgb,
+}
+
+impl TransferFunction {
+    pub fn encode(&self, value: Float) -> Float {
+        match self {
+            TransferFunction::Linear => value,
+            TransferFunction::Srgb if value <= 0.0031308 => 12.92 * value,
+            TransferFunction::Srgb => 1.055 * value.powf(1.0 / 2.4) - 0.055,
+        }
+    }
+
+    pub fn decode(&self, value: Float) -> Float {
+        match self {
+            TransferFunction::Linear => value,
+            TransferFunction::Srgb if value <= 0.04045 => value / 12.92,
+            TransferFunction::Srgb => ((value + 0.055) / 1.055).powf(2.4),
+        }
+    }
+}
+
+/// Output pipeline applied before writing a canvas: tone mapping followed by the
+/// transfer function.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Encoding {
+    pub tone_mapping: ToneMapping,
+    pub transfer: TransferFunction,
+}
+
+impl Default for Encoding {
+    fn default() -> Self {
+        Encoding {
+            tone_mapping: ToneMapping::Clamp,
+            transfer: TransferFunction::Linear,
+        }
+    }
+}
+
+impl Encoding {
+    pub fn encode(&self, color: Color) -> Color {
+        let color = self.tone_mapping.apply(color);
+        Color {
+            r: self.transfer.encode(color.r),
+            g: self.transfer.encode(color.g),
+            b: self.transfer.encode(color.b),
+        }
+    }
+}
+
+impl Canvas {
+    /// Returns a copy of the canvas with all pixels passed through the encoding.
+    ///
+    /// The result can be written with any of the `write_*` methods.
+    pub fn encode(&self, encoding: &Encoding) -> Canvas {
+        let mut canvas = self.clone();
+        for pixel in canvas.data.iter_mut() {
+            *pixel = encoding.encode(*pixel);
+        }
+        canvas
+    }
+
+    /// Returns a copy of the canvas with linear color values, for images that were
+    /// stored with the given transfer function.
+    pub fn decode(&self, transfer: TransferFunction) -> Canvas {
+        let mut canvas = self.clone();
+        for pixel in canvas.data.iter_mut() {
+            *pixel = Color {
+                r: transfer.decode(pixel.r),
+                g: transfer.decode(pixel.g),
+                b: transfer.decode(pixel.b),
+            };
+        }
+        canvas
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::approx_eq;
+    use std::error::Error;
+
+    const OPERATORS: [ToneMapping; 5] = [
+        ToneMapping::Clamp,
+        ToneMapping::Reinhard,
+        ToneMapping::ExtendedReinhard { white: 4.0 },
+        ToneMapping::AcesFilmic,
+        ToneMapping::Exposure { exposure: 1.0 },
+    ];
+
+    fn gray(value: Float) -> Color {
+        Color {
+            r: value,
+            g: value,
+            b: value,
+        }
+    }
+
+    #[test]
+    fn clamp_tone_mapping() {
+        let color = Color {
+            r: 1.9,
+            g: 0.5,
+            b: -0.5,
+        };
+        assert_eq!(
+            ToneMapping::Clamp.apply(color),
+            Color {
+                r: 1.0,
+                g: 0.5,
+                b: 0.0
+            }
+        );
+    }
+
+    #[test]
+    fn reinhard_tone_mapping() {
+        let mapped = ToneMapping::Reinhard.apply(Color {
+            r: 1.0,
+            g: 3.0,
+            b: 0.0,
+        });
+        assert!(approx_eq!(mapped.r, 0.5));
+        assert!(approx_eq!(mapped.g, 0.75));
+        assert!(approx_eq!(mapped.b, 0.0));
+    }
+
+    #[test]
+    fn extended_reinhard_maps_white_point_to_one() {
+        let mapping = ToneMapping::ExtendedReinhard { white: 4.0 };
+        assert!(approx_eq!(mapping.apply(gray(4.0)).r, 1.0));
+        assert!(approx_eq!(mapping.apply(gray(10.0)).r, 1.0));
+        assert!(approx_eq!(mapping.apply(gray(1.0)).r, 0.53125));
+    }
+
+    #[test]
+    fn aces_filmic_tone_mapping() {
+        let mapping = ToneMapping::AcesFilmic;
+        assert!(approx_eq!(mapping.apply(gray(0.0)).r, 0.0));
+        assert!(approx_eq!(mapping.apply(gray(1.0)).r, 0.80381, atol = 1e-5));
+        assert!(approx_eq!(mapping.apply(gray(100.0)).r, 1.0));
+    }
+
+    #[test]
+    fn exposure_tone_mapping() {
+        let mapping = ToneMapping::Exposure { exposure: 2.0 };
+        assert!(approx_eq!(
+            mapping.apply(gray(0.5)).r,
+            1.0 - Float::exp(-1.0)
+        ));
+    }
+
+    #[test]
+    fn tone_mapping_is_monotonic_and_bounded() {
+        for mapping in OPERATORS {
+            let mut last = 0.0;
+            for i in 0..=200 {
+                let value = mapping.apply(gray(i as Float * 0.1)).r;
+                assert!(value >= last, "{:?} is not monotonic", mapping);
+                assert!((0.0..=1.0).contains(&value));
+                last = value;
+            }
+        }
+    }
+
+    #[test]
+    fn srgb_transfer_function() {
+        let srgb = TransferFunction::Srgb;
+        assert_eq!(srgb.encode(0.0), 0.0);
+        assert!(approx_eq!(srgb.encode(1.0), 1.0));
+        assert!(approx_eq!(srgb.encode(0.001), 0.01292));
+        assert!(approx_eq!(srgb.encode(0.18), 0.46135, atol = 1e-5));
+        for i in 0..=100 {
+            let value = i as Float / 100.0;
+            assert!(approx_eq!(srgb.decode(srgb.encode(value)), value));
+        }
+    }
+
+    #[test]
+    fn linear_transfer_function_is_identity() {
+        assert_eq!(TransferFunction::Linear.encode(0.3), 0.3);
+        assert_eq!(TransferFunction::Linear.decode(0.3), 0.3);
+    }
+
+    #[test]
+    fn decode_canvas_after_reading() {
+        let mut canvas = Canvas::new(2, 1);
+        canvas.set_pixel(0, 0, gray(0.5)).unwrap();
+        canvas.set_pixel(1, 0, gray(1.0)).unwrap();
+        let decoded = canvas.decode(TransferFunction::Srgb);
+        let pixel = decoded.get_pixel(0, 0).unwrap();
+        assert!(approx_eq!(pixel.r, 0.21404, atol = 1e-5));
+        assert_eq!(pixel.r, pixel.b);
+        assert!(approx_eq!(decoded.get_pixel(1, 0).unwrap().g, 1.0));
+        assert_eq!(canvas.decode(TransferFunction::Linear), canvas);
+    }
+
+    #[test]
+    fn default_encoding_only_clamps() {
+        let color = Color {
+            r: 1.9,
+            g: 0.25,
+            b: -1.0,
+        };
+        assert_eq!(
+            Encoding::default().encode(color),
+            Color {
+                r: 1.0,
+                g: 0.25,
+                b: 0.0
+            }
+        );
+    }
+
+    #[test]
+    fn encode_canvas_before_writing() -> Result<(), Box<dyn Error>> {
+        let mut canvas = Canvas::new(2, 1);
+        canvas.set_pixel(0, 0, gray(1.0))?;
+        canvas.set_pixel(1, 0, gray(0.18))?;
+        let encoding = Encoding {
+            tone_mapping: ToneMapping::Reinhard,
+            transfer: TransferFunction::Srgb,
+        };
+        let encoded = canvas.encode(&encoding);
+        // The original canvas is left untouched.
+        assert_eq!(canvas.get_pixel(0, 0), Some(gray(1.0)));
+        assert!(approx_eq!(
+            encoded.get_pixel(0, 0).unwrap().r,
+            TransferFunction::Srgb.encode(0.5)
+        ));
+
+        let mut buffer = Vec::new();
+        encoded.write_ppm(&mut buffer)?;
+        let text = String::from_utf8(buffer)?;
+        // Reinhard maps 1.0 to 0.5 which is 188 in sRGB, 0.18 maps to 0.1525 (109).
+        assert_eq!(
+            text.lines().skip(3).collect::<Vec<_>>(),
+            ["188 188 188", "109 109 109"]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,176 @@
+use super::Canvas;
+use crate::{Color, Float};
+use std::io::{self, Write};
+
+impl Canvas {
+    /// Writes the canvas as Portable Float Map (little-endian), keeping the linear
+    /// color values without clamping.
+    pub fn write_pfm<T: Write>(&self, file: &mut T) -> Result<(), io::Error> {
+        // A negative scale marks little-endian data.
+        write!(file, "PF\n{} {}\n-1.0\n", self.width(), self.height())?;
+        let mut bytes = Vec::with_capacity(12 * self.width() * self.height());
+        // Rows are stored from bottom to top.
+        for y in (0..self.height()).rev() {
+            for x in 0..self.width() {
+                let pixel = self.get_pixel(x, y).expect("indices should be valid");
+                for value in [pixel.r, pixel.g, pixel.b] {
+                    bytes.extend((value as f32).to_le_bytes());
+                }
+            }
+        }
+        file.write_all(&bytes)
+    }
+
+    /// Writes the canvas as Radiance RGBE (.hdr) file with uncompressed scanlines.
+    ///
+    /// The format cannot store negative values, those are written as zero.
+    pub fn write_hdr<T: Write>(&self, file: &mut T) -> Result<(), io::Error> {
+        write!(
+            file,
+            "#?RADIANCE\nFORMAT=32-bit_rle_rgbe\n\n-Y {} +X {}\n",
+            self.height(),
+            self.width()
+        )?;
+        let mut bytes = Vec::with_capacity(4 * self.width() * self.height());
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                let pixel = self.get_pixel(x, y).expect("indices should be valid");
+                bytes.extend(to_rgbe(pixel));
+            }
+        }
+        file.write_all(&bytes)
+    }
+}
+
+// Encodes the color as shared exponent with an 8 bit mantissa per channel.
+fn to_rgbe(color: Color) -> [u8; 4] {
+    let (r, g, b) = (color.r.max(0.0), color.g.max(0.0), color.b.max(0.0));
+    let max = r.max(g).max(b);
+    if max < 1e-32 {
+        return [0; 4];
+    }
+    // Find the exponent such that max = mantissa * 2^exponent with mantissa in [0.5, 1).
+    let mut exponent = max.log2().floor() as i32 + 1;
+    if max / Float::powi(2.0, exponent) >= 1.0 {
+        exponent += 1;
+    }
+    let scale = 256.0 / Float::powi(2.0, exponent);
+    [
+        (r * scale) as u8,
+        (g * scale) as u8,
+        (b * scale) as u8,
+        (exponent + 128).clamp(0, 255) as u8,
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::approx_eq;
+    use std::error::Error;
+
+    fn test_canvas() -> Canvas {
+        let mut canvas = Canvas::new(3, 2);
+        let colors = [
+            Color {
+                r: 1.9,
+                g: 1.9,
+                b: 1.9,
+            },
+            Color::black(),
+            Color {
+                r: 0.25,
+                g: 12.5,
+                b: 1000.0,
+            },
+            Color {
+                r: 0.001,
+                g: 0.5,
+                b: 0.75,
+            },
+            Color::white(),
+            Color::yellow(),
+        ];
+        for (i, color) in colors.into_iter().enumerate() {
+            canvas.set_pixel(i % 3, i / 3, color).unwrap();
+        }
+        canvas
+    }
+
+    fn from_rgbe(bytes: &[u8]) -> Color {
+        if bytes[3] == 0 {
+            return Color::black();
+        }
+        let scale = Float::powi(2.0, bytes[3] as i32 - 128 - 8);
+        Color {
+            r: (bytes[0] as Float + 0.5) * scale,
+            g: (bytes[1] as Float + 0.5) * scale,
+            b: (bytes[2] as Float + 0.5) * scale,
+        }
+    }
+
+    #[test]
+    fn write_pfm_file() -> Result<(), Box<dyn Error>> {
+        let canvas = test_canvas();
+        let mut buffer = Vec::new();
+        canvas.write_pfm(&mut buffer)?;
+        let header = b"PF\n3 2\n-1.0\n";
+        assert_eq!(&buffer[..header.len()], header);
+        let values: Vec<f32> = buffer[header.len()..]
+            .chunks(4)
+            .map(|bytes| f32::from_le_bytes(bytes.try_into().unwrap()))
+            .collect();
+        assert_eq!(values.len(), 3 * 3 * 2);
+        // Bottom row comes first.
+        for (i, value) in values.chunks(3).enumerate() {
+            let (x, y) = (i % 3, 1 - i / 3);
+            let pixel = canvas.get_pixel(x, y).unwrap();
+            assert_eq!(value, [pixel.r as f32, pixel.g as f32, pixel.b as f32]);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn write_radiance_hdr_file() -> Result<(), Box<dyn Error>> {
+        let canvas = test_canvas();
+        let mut buffer = Vec::new();
+        canvas.write_hdr(&mut buffer)?;
+        let header = b"#?RADIANCE\nFORMAT=32-bit_rle_rgbe\n\n-Y 2 +X 3\n";
+        assert_eq!(&buffer[..header.len()], header);
+        let pixels = &buffer[header.len()..];
+        assert_eq!(pixels.len(), 4 * 3 * 2);
+        for (i, bytes) in pixels.chunks(4).enumerate() {
+            let expected = canvas.get_pixel(i % 3, i / 3).unwrap();
+            let actual = from_rgbe(bytes);
+            // The mantissa has 8 bits relative to the largest channel.
+            let atol = expected.r.max(expected.g).max(expected.b) / 128.0;
+            assert!(approx_eq!(actual.r, expected.r, atol = atol));
+            assert!(approx_eq!(actual.g, expected.g, atol = atol));
+            assert!(approx_eq!(actual.b, expected.b, atol = atol));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn rgbe_encoding() {
+        assert_eq!(to_rgbe(Color::black()), [0, 0, 0, 0]);
+        assert_eq!(to_rgbe(Color::white()), [128, 128, 128, 129]);
+        assert_eq!(to_rgbe(Color::red()), [128, 0, 0, 129]);
+        assert_eq!(
+            to_rgbe(Color {
+                r: 0.5,
+                g: 0.25,
+                b: -1.0
+            }),
+            [128, 64, 0, 128]
+        );
+        assert_eq!(
+            to_rgbe(Color {
+                r: 1.9,
+                g: 0.0,
+                b: 0.0
+            }),
+            [243, 0, 0, 129]
+        );
+    }
+}
@@ -0,0 +1,339 @@
+use super::{deflate::zlib_compress, to_byte, Canvas};
+use std::io::{self, Write};
+
+const SIGNATURE: [u8; 8] = [137, 80, 78, 71, 13, 10, 26, 10];
+const CRC_TABLE: [u32; 256] = crc_table();
+
+impl Canvas {
+    /// Writes the canvas as 8 bit RGB PNG file.
+    pub fn write_png<T: Write>(&self, file: &mut T) -> Result<(), io::Error> {
+        self.write_png_with_channels(file, 3)
+    }
+
+    /// Writes the canvas as 8 bit RGBA PNG file, all pixels are fully opaque.
+    pub fn write_png_rgba<T: Write>(&self, file: &mut T) -> Result<(), io::Error> {
+        self.write_png_with_channels(file, 4)
+    }
+
+    fn write_png_with_channels<T: Write>(
+        &self,
+        file: &mut T,
+        channels: usize,
+    ) -> Result<(), io::Error> {
+        let size_error = || {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "cannot write canvas with width={} and height={} as PNG",
+                    self.width(),
+                    self.height()
+                ),
+            )
+        };
+        let width = u32::try_from(self.width()).map_err(|_| size_error())?;
+        let height = u32::try_from(self.height()).map_err(|_| size_error())?;
+        if width == 0 || height == 0 {
+            return Err(size_error());
+        }
+
+        file.write_all(&SIGNATURE)?;
+
+        let mut header = Vec::with_capacity(13);
+        header.extend(width.to_be_bytes());
+        header.extend(height.to_be_bytes());
+        let color_type = if channels == 4 { 6 } else { 2 };
+        // Bit depth, color type, compression, filter and interlace method.
+        header.extend([8, color_type, 0, 0, 0]);
+        write_chunk(file, b"IHDR", &header)?;
+
+        let row_length = channels * self.width();
+        let mut previous = vec![0; row_length];
+        let mut filtered = Vec::with_capacity((row_length + 1) * self.height());
+        for y in 0..self.height() {
+            let mut row = Vec::with_capacity(row_length);
+            for x in 0..self.width() {
+                let pixel = self.get_pixel(x, y).expect("indices should be valid");
+                row.extend([to_byte(pixel.r), to_byte(pixel.g), to_byte(pixel.b)]);
+                if channels == 4 {
+                    row.push(255);
+                }
+            }
+            filter_row(&row, &previous, channels, &mut filtered);
+            previous = row;
+        }
+        write_chunk(file, b"IDAT", &zlib_compress(&filtered))?;
+        write_chunk(file, b"IEND", &[])
+    }
+}
+
+fn write_chunk<T: Write>(file: &mut T, kind: &[u8; 4], data: &[u8]) -> Result<(), io::Error> {
+    let length = u32::try_from(data.len())
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "PNG chunk too large"))?;
+    file.write_all(&length.to_be_bytes())?;
+    file.write_all(kind)?;
+    file.write_all(data)?;
+    let crc = !update_crc(update_crc(!0, kind), data);
+    file.write_all(&crc.to_be_bytes())
+}
+
+const fn crc_table() -> [u32; 256] {
+    let mut table = [0; 256];
+    let mut n = 0;
+    while n < 256 {
+        let mut c = n as u32;
+        let mut k = 0;
+        while k < 8 {
+            c = if c & 1 == 1 {
+                0xEDB88320 ^ (c >> 1)
+            } else {
+                c >> 1
+            };
+            k += 1;
+        }
+        table[n] = c;
+        n += 1;
+    }
+    table
+}
+
+fn update_crc(crc: u32, data: &[u8]) -> u32 {
+    data.iter().fold(crc, |crc, &byte| {
+        CRC_TABLE[((crc ^ byte as u32) & 0xFF) as usize] ^ (crc >> 8)
+    })
+}
+
+fn paeth(a: u8, b: u8, c: u8) -> u8 {
+    let p = a as i16 + b as i16 - c as i16;
+    let (pa, pb, pc) = (
+        (p - a as i16).abs(),
+        (p - b as i16).abs(),
+        (p - c as i16).abs(),
+    );
+    if pa <= pb && pa <= pc {
+        a
+    } else if pb <= pc {
+        b
+    } else {
+        c
+    }
+}
+
+// Applies every PNG filter type to the row and appends the one that is likely to
+// compress best (smallest sum of absolute values) together with its type byte.
+fn filter_row(row: &[u8], previous: &[u8], bpp: usize, out: &mut Vec<u8>) {
+    let left = |i: usize| if i >= bpp { row[i - bpp] } else { 0 };
+    let upper_left = |i: usize| if i >= bpp { previous[i - bpp] } else { 0 };
+    let predictors: [&dyn Fn(usize) -> u8; 5] = [
+        &|_| 0,
+        &left,
+        &|i| previous[i],
+        &|i| ((left(i) as u16 + previous[i] as u16) / 2) as u8,
+        &|i| paeth(left(i), previous[i], upper_left(i)),
+    ];
+    let mut best: Option<(u64, u8, Vec<u8>)> = None;
+    for (filter_type, predictor) in predictors.iter().enumerate() {
+        let filtered: Vec<u8> = (0..row.len())
+            .map(|i| row[i].wrapping_sub(predictor(i)))
+            .collect();
+        let cost = filtered
+            .iter()
+            .map(|&b| (b as i8).unsigned_abs() as u64)
+            .sum();
+        if best
+            .as_ref()
+            .is_none_or(|(best_cost, _, _)| cost < *best_cost)
+        {
+            best = Some((cost, filter_type as u8, filtered));
+        }
+    }
+    let (_, filter_type, filtered) = best.expect("there should be at least one filter");
+    out.push(filter_type);
+    out.extend(filtered);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::deflate::zlib_decompress;
+    use super::*;
+    use crate::Color;
+    use std::error::Error;
+
+    struct DecodedPng {
+        width: usize,
+        height: usize,
+        channels: usize,
+        data: Vec<u8>,
+    }
+
+    // Decodes PNG files as written by `write_png`, verifying checksums on the way.
+    fn decode_png(bytes: &[u8]) -> Result<DecodedPng, Box<dyn Error>> {
+        if bytes[..8] != SIGNATURE {
+            return Err("invalid signature".into());
+        }
+        let mut pos = 8;
+        let mut header = None;
+        let mut compressed = Vec::new();
+        loop {
+            let length = u32::from_be_bytes(bytes[pos..pos + 4].try_into()?) as usize;
+            let kind = &bytes[pos + 4..pos + 8];
+            let data = &bytes[pos + 8..pos + 8 + length];
+            let crc = u32::from_be_bytes(bytes[pos + 8 + length..pos + 12 + length].try_into()?);
+            if crc != !update_crc(update_crc(!0, kind), data) {
+                return Err("CRC mismatch".into());
+            }
+            pos += 12 + length;
+            match kind {
+                b"IHDR" => header = Some(data.to_vec()),
+                b"IDAT" => compressed.extend(data),
+                b"IEND" => break,
+                _ => return Err("unexpected chunk".into()),
+            }
+        }
+        if pos != bytes.len() {
+            return Err("trailing data after IEND".into());
+        }
+        let header = header.ok_or("missing IHDR")?;
+        let width = u32::from_be_bytes(header[0..4].try_into()?) as usize;
+        let height = u32::from_be_bytes(header[4..8].try_into()?) as usize;
+        let channels = match header[8..13] {
+            [8, 2, 0, 0, 0] => 3,
+            [8, 6, 0, 0, 0] => 4,
+            _ => return Err("unsupported format".into()),
+        };
+
+        let filtered = zlib_decompress(&compressed)?;
+        let row_length = width * channels;
+        if filtered.len() != height * (row_length + 1) {
+            return Err("unexpected size of image data".into());
+        }
+        let mut data: Vec<u8> = Vec::with_capacity(width * height * channels);
+        for (y, line) in filtered.chunks(row_length + 1).enumerate() {
+            let start = y * row_length;
+            for i in 0..row_length {
+                let a = if i >= channels {
+                    data[start + i - channels]
+                } else {
+                    0
+                };
+                let b = if y > 0 {
+                    data[start + i - row_length]
+                } else {
+                    0
+                };
+                let c = if y > 0 && i >= channels {
+                    data[start + i - row_length - channels]
+                } else {
+                    0
+                };
+                let prediction = match line[0] {
+                    0 => 0,
+                    1 => a,
+                    2 => b,
+                    3 => ((a as u16 + b as u16) / 2) as u8,
+                    4 => paeth(a, b, c),
+                    _ => return Err("invalid filter type".into()),
+                };
+                data.push(line[1 + i].wrapping_add(prediction));
+            }
+        }
+        Ok(DecodedPng {
+            width,
+            height,
+            channels,
+            data,
+        })
+    }
+
+    fn test_canvas() -> Canvas {
+        let mut canvas = Canvas::new(13, 7);
+        for y in 0..canvas.height() {
+            for x in 0..canvas.width() {
+                let color = Color {
+                    r: x as f64 / 12.0,
+                    g: y as f64 / 6.0,
+                    b: ((x * y) % 5) as f64 / 4.0,
+                };
+                canvas.set_pixel(x, y, color).unwrap();
+            }
+        }
+        // Out-of-range values should be clamped.
+        let (r, g, b) = (1.7, -0.3, 0.5);
+        canvas.set_pixel(4, 2, Color { r, g, b }).unwrap();
+        canvas
+    }
+
+    #[test]
+    fn crc_of_known_string() {
+        assert_eq!(!update_crc(!0, b"123456789"), 0xCBF43926);
+        assert_eq!(!update_crc(!0, b"IEND"), 0xAE426082);
+    }
+
+    #[test]
+    fn write_rgb_png() -> Result<(), Box<dyn Error>> {
+        let canvas = test_canvas();
+        let mut buffer = Vec::new();
+        canvas.write_png(&mut buffer)?;
+        let png = decode_png(&buffer)?;
+        assert_eq!(png.width, 13);
+        assert_eq!(png.height, 7);
+        assert_eq!(png.channels, 3);
+        for y in 0..canvas.height() {
+            for x in 0..canvas.width() {
+                let pixel = canvas.get_pixel(x, y).unwrap();
+                let i = 3 * (y * canvas.width() + x);
+                assert_eq!(
+                    png.data[i..i + 3],
+                    [to_byte(pixel.r), to_byte(pixel.g), to_byte(pixel.b)]
+                );
+            }
+        }
+        assert_eq!(
+            png.data[3 * (2 * 13 + 4)..3 * (2 * 13 + 4) + 3],
+            [255, 0, 128]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn write_rgba_png() -> Result<(), Box<dyn Error>> {
+        let canvas = test_canvas();
+        let mut buffer = Vec::new();
+        canvas.write_png_rgba(&mut buffer)?;
+        let png = decode_png(&buffer)?;
+        assert_eq!(png.channels, 4);
+        for y in 0..canvas.height() {
+            for x in 0..canvas.width() {
+                let pixel = canvas.get_pixel(x, y).unwrap();
+                let i = 4 * (y * canvas.width() + x);
+                assert_eq!(
+                    png.data[i..i + 4],
+                    [to_byte(pixel.r), to_byte(pixel.g), to_byte(pixel.b), 255]
+                );
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn uniform_png_is_small() -> Result<(), Box<dyn Error>> {
+        let mut canvas = Canvas::new(256, 256);
+        for y in 0..256 {
+            for x in 0..256 {
+                canvas.set_pixel(x, y, Color::magenta())?;
+            }
+        }
+        let mut buffer = Vec::new();
+        canvas.write_png(&mut buffer)?;
+        assert!(buffer.len() < 2000);
+        let png = decode_png(&buffer)?;
+        assert!(png.data.chunks(3).all(|pixel| pixel == [255, 0, 255]));
+        Ok(())
+    }
+
+    #[test]
+    fn writing_empty_canvas_fails() {
+        let mut buffer = Vec::new();
+        let error = Canvas::new(0, 5).write_png(&mut buffer).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+    }
+}
@@ -0,0 +1,377 @@
+use super::{to_byte, Canvas};
+use crate::{Color, Float};
+use std::io::{self, Read, Write};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum PpmError {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error("invalid PPM file: {0}")]
+    Format(String),
+}
+
+impl Canvas {
+    /// Writes the canvas as plain (ASCII) PPM file with one pixel per line.
+    pub fn write_ppm<T: Write>(&self, file: &mut T) -> Result<(), io::Error> {
+        // Write header.
+        writeln!(file, "P3")?;
+        writeln!(file, "{} {}", self.width(), self.height())?;
+        writeln!(file, "255")?;
+        // Write pixel data.
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                let pixel = self.get_pixel(x, y).expect("indices should be valid");
+                let (r, g, b) = (to_byte(pixel.r), to_byte(pixel.g), to_byte(pixel.b));
+                writeln!(file, "{} {} {}", r, g, b)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes the canvas as binary (raw) PPM file.
+    pub fn write_ppm_binary<T: Write>(&self, file: &mut T) -> Result<(), io::Error> {
+        write!(file, "P6\n{} {}\n255\n", self.width(), self.height())?;
+        let mut bytes = Vec::with_capacity(3 * self.width() * self.height());
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                let pixel = self.get_pixel(x, y).expect("indices should be valid");
+                bytes.extend([to_byte(pixel.r), to_byte(pixel.g), to_byte(pixel.b)]);
+            }
+        }
+        file.write_all(&bytes)
+    }
+
+    /// Reads a plain (P3) or binary (P6) PPM file.
+    pub fn read_ppm<T: Read>(file: &mut T) -> Result<Canvas, PpmError> {
+        let mut data = Vec::new();
+        file.read_to_end(&mut data)?;
+        let mut parser = Parser {
+            data: &data,
+            pos: 0,
+        };
+
+        let binary = match parser.token()? {
+            b"P3" => false,
+            b"P6" => true,
+            magic => {
+                return Err(PpmError::Format(format!(
+                    "unsupported magic number {:?}, expected P3 or P6",
+                    String::from_utf8_lossy(magic)
+                )))
+            }
+        };
+        let width = parser.number("width")?;
+        let height = parser.number("height")?;
+        let maxval = parser.number("maxval")?;
+        if maxval == 0 || maxval > 65535 {
+            return Err(PpmError::Format(format!(
+                "maxval must be between 1 and 65535, got {}",
+                maxval
+            )));
+        }
+
+        if binary {
+            // Exactly one whitespace character separates the header from the raster.
+            parser.pos += 1;
+        }
+        // Check the size against the remaining data before allocating the canvas, so
+        // that a short file claiming a huge size cannot exhaust the memory.
+        let remaining = data.len().saturating_sub(parser.pos);
+        let fits = match width
+            .checked_mul(height)
+            .and_then(|pixels| pixels.checked_mul(3))
+        {
+            Some(samples) if binary => {
+                let sample_size = if maxval > 255 { 2 } else { 1 };
+                samples
+                    .checked_mul(sample_size)
+                    .is_some_and(|size| size <= remaining)
+            }
+            // Plain samples take at least one digit and a separator, except the last.
+            Some(samples) => samples <= remaining.div_ceil(2),
+            None => false,
+        };
+        if !fits {
+            return Err(PpmError::Format(format!(
+                "size {}x{} exceeds the pixel data",
+                width, height
+            )));
+        }
+
+        let mut canvas = Canvas::new(width, height);
+        let mut sample = || -> Result<Float, PpmError> {
+            let value = if binary {
+                parser.binary_sample(maxval > 255)?
+            } else {
+                parser.number("sample")?
+            };
+            if value > maxval {
+                return Err(PpmError::Format(format!(
+                    "sample {} exceeds maxval {}",
+                    value, maxval
+                )));
+            }
+            Ok(value as Float / maxval as Float)
+        };
+        for y in 0..height {
+            for x in 0..width {
+                let (r, g, b) = (sample()?, sample()?, sample()?);
+                canvas
+                    .set_pixel(x, y, Color { r, g, b })
+                    .expect("indices should be valid");
+            }
+        }
+        Ok(canvas)
+    }
+}
+
+struct Parser<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    // Skips whitespace and comments, which run from '#' to the end of the line.
+    fn skip_whitespace(&mut self) {
+        while let Some(&byte) = self.data.get(self.pos) {
+            if byte == b'#' {
+                while self.data.get(self.pos).is_some_and(|&b| b != b'\n') {
+                    self.pos += 1;
+                }
+            } else if byte.is_ascii_whitespace() {
+                self.pos += 1;
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn token(&mut self) -> Result<&'a [u8], PpmError> {
+        self.skip_whitespace();
+        let start = self.pos;
+        while self
+            .data
+            .get(self.pos)
+            .is_some_and(|&b| !b.is_ascii_whitespace() && b != b'#')
+        {
+            self.pos += 1;
+        }
+        if start == self.pos {
+            return Err(PpmError::Format(String::from("unexpected end of file")));
+        }
+        Ok(&self.data[start..self.pos])
+    }
+
+    fn number(&mut self, name: &str) -> Result<usize, PpmError> {
+        let token = self.token()?;
+        std::str::from_utf8(token)
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .ok_or_else(|| {
+                PpmError::Format(format!(
+                    "expected {}, got {:?}",
+                    name,
+                    String::from_utf8_lossy(token)
+                ))
+            })
+    }
+
+    // Samples are one byte, or two bytes in big-endian order if maxval exceeds 255.
+    fn binary_sample(&mut self, wide: bool) -> Result<usize, PpmError> {
+        let len = if wide { 2 } else { 1 };
+        let bytes = self
+            .data
+            .get(self.pos..self.pos + len)
+            .ok_or_else(|| PpmError::Format(String::from("unexpected end of pixel data")))?;
+        self.pos += len;
+        Ok(bytes.iter().fold(0, |acc, &b| (acc << 8) | b as usize))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::approx_eq;
+    use std::error::Error;
+    use std::fs::File;
+    use std::io::Read;
+    use tempfile::NamedTempFile;
+
+    macro_rules! assert_color_approx_eq {
+        ($color1:expr, $color2:expr) => {
+            assert!(approx_eq!($color1.r, $color2.r));
+            assert!(approx_eq!($color1.g, $color2.g));
+            assert!(approx_eq!($color1.b, $color2.b));
+        };
+    }
+
+    #[test]
+    fn save_canvas_to_ppm_file() -> Result<(), Box<dyn Error>> {
+        // Open file handles.
+        let mut file_w = NamedTempFile::new()?; // Write handle.
+        let mut file_r: File = file_w.reopen()?; // Read handle.
+
+        // Create canvas and setup pixel data.
+        let mut canvas = Canvas::new(3, 2);
+        canvas.set_pixel(0, 0, Color::red())?;
+        canvas.set_pixel(1, 0, Color::green())?;
+        canvas.set_pixel(2, 0, Color::blue())?;
+        canvas.set_pixel(0, 1, Color::yellow())?;
+        // Last two pixels check that clamping works correctly.
+        let (r, g, b) = (1.1, 2.0, 1.0); // Should be clamped to white.
+        canvas.set_pixel(1, 1, Color { r, g, b })?;
+        let (r, g, b) = (0.0, -0.2, -1.0); // Should be clamped to black.
+        canvas.set_pixel(2, 1, Color { r, g, b })?;
+
+        // Check that canves is written correctly.
+        canvas.write_ppm(&mut file_w)?;
+        let mut buffer = String::new();
+        file_r.read_to_string(&mut buffer)?;
+        let mut lines = buffer.lines();
+        assert_eq!(Some("P3"), lines.next());
+        assert_eq!(Some("3 2"), lines.next());
+        assert_eq!(Some("255"), lines.next());
+        assert_eq!(Some("255 0 0"), lines.next()); // Red pixel at (0, 0).
+        assert_eq!(Some("0 255 0"), lines.next()); // Green pixel at (1, 0).
+        assert_eq!(Some("0 0 255"), lines.next()); // Blue pixel at (2, 0).
+        assert_eq!(Some("255 255 0"), lines.next()); // Yellow pixel at (0, 1).
+        assert_eq!(Some("255 255 255"), lines.next()); // White pixel at (1, 1).
+        assert_eq!(Some("0 0 0"), lines.next()); // Black pixel at (2, 1).
+        assert_eq!(None, lines.next()); // File should have ended.
+        Ok(())
+    }
+
+    #[test]
+    fn save_canvas_to_binary_ppm_file() -> Result<(), Box<dyn Error>> {
+        let mut canvas = Canvas::new(2, 2);
+        canvas.set_pixel(0, 0, Color::red())?;
+        canvas.set_pixel(1, 0, Color::gray())?;
+        canvas.set_pixel(0, 1, Color::cyan())?;
+        let (r, g, b) = (1.5, -0.5, 0.2); // Should be clamped.
+        canvas.set_pixel(1, 1, Color { r, g, b })?;
+
+        let mut buffer = Vec::new();
+        canvas.write_ppm_binary(&mut buffer)?;
+        let header = b"P6\n2 2\n255\n";
+        assert_eq!(&buffer[..header.len()], header);
+        assert_eq!(
+            &buffer[header.len()..],
+            &[255, 0, 0, 128, 128, 128, 0, 255, 255, 255, 0, 51]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn read_plain_ppm_with_comments() -> Result<(), Box<dyn Error>> {
+        let data = b"P3\n# Created by hand.\n2 1 # width and height\n\
+            # Comment between header and pixels.\n10\n10 5 0   0 0 10\n";
+        let canvas = Canvas::read_ppm(&mut &data[..])?;
+        assert_eq!(canvas.width(), 2);
+        assert_eq!(canvas.height(), 1);
+        assert_color_approx_eq!(
+            canvas.get_pixel(0, 0).unwrap(),
+            Color {
+                r: 1.0,
+                g: 0.5,
+                b: 0.0,
+            }
+        );
+        assert_color_approx_eq!(canvas.get_pixel(1, 0).unwrap(), Color::blue());
+        Ok(())
+    }
+
+    #[test]
+    fn read_binary_ppm_with_two_byte_samples() -> Result<(), Box<dyn Error>> {
+        let mut data = b"P6 1 1 # comment\n1000\n".to_vec();
+        data.extend([0x03, 0xE8, 0x01, 0xF4, 0x00, 0x00]); // 1000, 500, 0.
+        let canvas = Canvas::read_ppm(&mut &data[..])?;
+        assert_color_approx_eq!(
+            canvas.get_pixel(0, 0).unwrap(),
+            Color {
+                r: 1.0,
+                g: 0.5,
+                b: 0.0,
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn round_trip_binary_ppm() -> Result<(), Box<dyn Error>> {
+        let mut canvas = Canvas::new(3, 2);
+        for (i, color) in [
+            Color::red(),
+            Color::green(),
+            Color::blue(),
+            Color::yellow(),
+            Color::white(),
+            Color::gray(),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            canvas.set_pixel(i % 3, i / 3, color)?;
+        }
+        let mut file_w = NamedTempFile::new()?;
+        let mut file_r: File = file_w.reopen()?;
+        canvas.write_ppm_binary(&mut file_w)?;
+        let read = Canvas::read_ppm(&mut file_r)?;
+        assert_eq!(read.width(), 3);
+        assert_eq!(read.height(), 2);
+        for (expected, actual) in canvas.into_iter().zip(read) {
+            assert!(approx_eq!(actual.r, expected.r, atol = 0.5 / 255.0));
+            assert!(approx_eq!(actual.g, expected.g, atol = 0.5 / 255.0));
+            assert!(approx_eq!(actual.b, expected.b, atol = 0.5 / 255.0));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn round_trip_plain_ppm() -> Result<(), Box<dyn Error>> {
+        let mut canvas = Canvas::new(2, 1);
+        canvas.set_pixel(0, 0, Color::magenta())?;
+        canvas.set_pixel(1, 0, Color::cyan())?;
+        let mut buffer = Vec::new();
+        canvas.write_ppm(&mut buffer)?;
+        let read = Canvas::read_ppm(&mut &buffer[..])?;
+        assert_eq!(read.get_pixel(0, 0), Some(Color::magenta()));
+        assert_eq!(read.get_pixel(1, 0), Some(Color::cyan()));
+        Ok(())
+    }
+
+    #[test]
+    fn reading_invalid_ppm_fails() {
+        let errors = [
+            &b"P5\n1 1\n255\n\x00"[..],
+            b"P3\n1 1\n",
+            b"P3\n1 x\n255\n0 0 0\n",
+            b"P3\n1 1\n0\n0 0 0\n",
+            b"P3\n1 1\n255\n0 0 256\n",
+            b"P3\n2 1\n255\n0 0 0\n",
+            b"P6\n2 1\n255\n\x00\x00\x00\x00",
+        ];
+        for data in errors {
+            assert!(matches!(
+                Canvas::read_ppm(&mut &data[..]),
+                Err(PpmError::Format(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn reading_ppm_claiming_huge_size_fails() {
+        let errors = [
+            &b"P6\n100000 100000\n255\n\x00"[..],
+            b"P6\n100000 100000\n65535\n\x00",
+            b"P6\n18446744073709551615 2\n255\n\x00",
+            b"P3\n100000 100000\n255\n0 0 0\n",
+        ];
+        for data in errors {
+            assert!(matches!(
+                Canvas::read_ppm(&mut &data[..]),
+                Err(PpmError::Format(_))
+            ));
+        }
+    }
+}
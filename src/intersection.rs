@@ -1,40 +1,822 @@
-use crate::{primitives::float::Float, Sphere};
+use crate::{epsilon, primitives::float::Float, Material, Point, Ray, Shape, TangentFrame, Vector};
 
-#[derive(Debug, PartialEq, Clone, Copy)]
-pub struct Intersection {
+#[derive(Debug, PartialEq, Clone)]
+pub struct Intersection<'a> {
     pub t: Float,
-    pub object: Sphere,
+    pub object: &'a dyn Shape,
+    /// Barycentric coordinates of the hit, only meaningful for triangles.
+    pub u: Float,
+    pub v: Float,
+    /// Groups containing the object, innermost first.
+    pub parents: Vec<&'a dyn Shape>,
+}
+
+/// Precomputed values needed for shading an intersection.
+#[derive(Debug, Clone, Copy)]
+pub struct Computations<'a> {
+    pub t: Float,
+    pub object: &'a dyn Shape,
+    /// Material for shading the object, see `Intersection::material`.
+    pub material: &'a Material,
+    pub point: Point,
+    /// The over point in object space of the intersected object, used for patterns.
+    pub object_point: Point,
+    /// Texture coordinates stored on the surface at the hit point, if any.
+    pub texture_coordinates: Option<(Float, Float)>,
+    pub eye: Vector,
+    /// Normal used for shading, which includes the normal map of the material.
+    pub normal: Vector,
+    /// Tangent frame around `normal`, pointing towards increasing texture coordinates.
+    /// Only computed for materials with a normal perturbation.
+    pub frame: Option<TangentFrame>,
+    pub inside: bool,
+    /// The hit point slightly moved along the normal, used as origin for shadow rays.
+    pub over_point: Point,
+    /// Direction of the ray after being reflected at the surface.
+    pub reflectv: Vector,
+    /// Refractive indices of the materials the ray is leaving and entering.
+    pub n1: Float,
+    pub n2: Float,
+    /// The hit point slightly moved below the surface, used as origin for refracted rays.
+    pub under_point: Point,
+}
+
+impl<'a> Intersection<'a> {
+    pub fn new(t: Float, object: &'a dyn Shape) -> Self {
+        Self::with_uv(t, object, 0.0, 0.0)
+    }
+
+    pub fn with_uv(t: Float, object: &'a dyn Shape, u: Float, v: Float) -> Self {
+        Intersection {
+            t,
+            object,
+            u,
+            v,
+            parents: vec![],
+        }
+    }
+
+    /// Converts a point from world space into object space of the intersected object.
+    pub fn world_to_object(&self, point: Point) -> Point {
+        self.parents
+            .iter()
+            .rev()
+            .chain([&self.object])
+            .fold(point, |point, shape| shape.point_to_object_space(point))
+    }
+
+    /// Converts a normal from object space of the intersected object into world space.
+    pub fn normal_to_world(&self, normal: Vector) -> Vector {
+        [&self.object]
+            .into_iter()
+            .chain(&self.parents)
+            .fold(normal, |normal, shape| {
+                shape.normal_from_object_space(normal)
+            })
+    }
+
+    /// Material of the intersected object, or the one shared by the innermost parent
+    /// providing one, like a `Mesh`.
+    pub fn material(&self) -> &'a Material {
+        self.parents
+            .iter()
+            .find_map(|parent| parent.shared_material())
+            .unwrap_or_else(|| self.object.material())
+    }
+
+    // Shape providing the material, which is the same for all triangles of a mesh.
+    fn material_owner(&self) -> &'a dyn Shape {
+        self.parents
+            .iter()
+            .copied()
+            .find(|parent| parent.shared_material().is_some())
+            .unwrap_or(self.object)
+    }
+
+    /// Converts a direction from object space of the intersected object into world space.
+    pub fn vector_to_world(&self, vector: Vector) -> Vector {
+        [&self.object]
+            .into_iter()
+            .chain(&self.parents)
+            .fold(vector, |vector, shape| {
+                shape.vector_from_object_space(vector)
+            })
+    }
+
+    /// Normal at a point on the intersected object, taking all parent groups into account.
+    pub fn normal_at(&self, world_point: Point) -> Vector {
+        let object_point = self.world_to_object(world_point);
+        let object_normal = self.object.local_normal_at_uv(object_point, self.u, self.v);
+        self.normal_to_world(object_normal)
+    }
+
+    /// Precomputes the values needed for shading this intersection, `intersections`
+    /// has to be sorted by `t` and is needed to find the refractive indices.
+    pub fn prepare_computations(
+        &self,
+        ray: &Ray,
+        intersections: &[Intersection<'a>],
+    ) -> Computations<'a> {
+        let point = ray.position(self.t);
+        let eye = -ray.direction;
+        let surface_point = self.world_to_object(point);
+        let object_normal = self
+            .object
+            .local_normal_at_uv(surface_point, self.u, self.v);
+        let mut geometric_normal = self.normal_to_world(object_normal);
+        let material = self.material();
+        let texture_coordinates = self.object.texture_coordinates_at(self.u, self.v);
+        let mut frame = material.normal_perturbation.as_ref().map(|perturbation| {
+            let local_frame =
+                TangentFrame::new(object_normal, self.object.local_tangent_at(surface_point));
+            TangentFrame::new(
+                self.normal_to_world(perturbation.perturb(
+                    surface_point,
+                    texture_coordinates,
+                    &local_frame,
+                )),
+                self.vector_to_world(local_frame.tangent),
+            )
+        });
+        let mut normal = frame.map_or(geometric_normal, |frame| frame.normal);
+        // Flip the normals if the ray originates inside the object.
+        let inside = geometric_normal.dot(eye) < 0.0;
+        if inside {
+            geometric_normal = -geometric_normal;
+            normal = -normal;
+            // The tangent keeps following u, so the bitangent flips with the normal.
+            frame = frame.map(|frame| TangentFrame {
+                bitangent: -frame.bitangent,
+                normal,
+                ..frame
+            });
+        }
+        let (n1, n2) = self.refractive_indices(intersections);
+        // Offset along the geometric normal, the shading normal might point into the surface.
+        // Patterns are evaluated at the over point too, so that they agree with the shadow test.
+        let over_point = point + geometric_normal * epsilon();
+        Computations {
+            t: self.t,
+            object: self.object,
+            material,
+            point,
+            object_point: self.world_to_object(over_point),
+            texture_coordinates,
+            eye,
+            normal,
+            frame,
+            inside,
+            over_point,
+            reflectv: ray.direction.reflect(normal),
+            n1,
+            n2,
+            under_point: point - geometric_normal * epsilon(),
+        }
+    }
+
+    // Tracks which objects contain the ray along the intersections to find the
+    // refractive indices on both sides of this intersection.
+    fn refractive_indices(&self, intersections: &[Intersection<'a>]) -> (Float, Float) {
+        let mut containers: Vec<&Intersection> = vec![];
+        let refractive_index = |containers: &[&Intersection]| match containers.last() {
+            Some(container) => container.material().refractive_index,
+            None => 1.0,
+        };
+        let mut n1 = 1.0;
+        for intersection in intersections {
+            let is_hit = intersection == self;
+            if is_hit {
+                n1 = refractive_index(&containers);
+            }
+            let owner = intersection.material_owner();
+            match containers
+                .iter()
+                .position(|container| container.material_owner() == owner)
+            {
+                Some(index) => {
+                    containers.remove(index);
+                }
+                None => containers.push(intersection),
+            }
+            if is_hit {
+                return (n1, refractive_index(&containers));
+            }
+        }
+        (1.0, 1.0)
+    }
+}
+
+impl Computations<'_> {
+    /// Fraction of light that is reflected, using Schlick's approximation of the
+    /// Fresnel equations.
+    pub fn schlick(&self) -> Float {
+        let mut cos = self.eye.dot(self.normal);
+        if self.n1 > self.n2 {
+            let n = self.n1 / self.n2;
+            let sin2_t = n * n * (1.0 - cos * cos);
+            // Total internal reflection.
+            if sin2_t > 1.0 {
+                return 1.0;
+            }
+            cos = Float::sqrt(1.0 - sin2_t);
+        }
+        let r0 = ((self.n1 - self.n2) / (self.n1 + self.n2)).powi(2);
+        r0 + (1.0 - r0) * (1.0 - cos).powi(5)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{
+        approx_eq, BumpMap, CheckerPattern, CheckerTexture, Color, Group, Material, Matrix4x4,
+        Mesh, MeshTriangle, NormalMap, Plane, SolidPattern, Sphere, Transform, Triangle, UvMapping,
+    };
+    use std::sync::Arc;
 
     #[test]
     fn create_intersection() {
         let sphere = Sphere::default();
-        let intersection = Intersection {
-            t: 3.5,
-            object: sphere,
-        };
+        let intersection = Intersection::new(3.5, &sphere);
         assert_eq!(intersection.t, 3.5);
-        assert_eq!(intersection.object, sphere);
+        assert_eq!(intersection.object, &sphere as &dyn Shape);
+    }
+
+    #[test]
+    fn intersection_encapsulates_u_and_v() {
+        let triangle = Triangle::new(
+            Point {
+                x: 0.0,
+                y: 1.0,
+                z: 0.0,
+            },
+            Point {
+                x: -1.0,
+                y: 0.0,
+                z: 0.0,
+            },
+            Point {
+                x: 1.0,
+                y: 0.0,
+                z: 0.0,
+            },
+        );
+        let intersection = Intersection::with_uv(3.5, &triangle, 0.2, 0.4);
+        assert_eq!(intersection.u, 0.2);
+        assert_eq!(intersection.v, 0.4);
     }
 
     #[test]
     fn aggregate_intersections() {
         let sphere = Sphere::default();
-        let i1 = Intersection {
-            t: 1.0,
-            object: sphere,
-        };
-        let i2 = Intersection {
-            t: 2.0,
-            object: sphere,
-        };
+        let i1 = Intersection::new(1.0, &sphere);
+        let i2 = Intersection::new(2.0, &sphere);
         let intersections = [i1, i2];
         assert_eq!(intersections.len(), 2);
         assert_eq!(intersections.first().unwrap().t, 1.0);
         assert_eq!(intersections.last().unwrap().t, 2.0);
     }
+
+    #[test]
+    fn precompute_state_of_intersection() {
+        let ray = Ray {
+            origin: Point {
+                x: 0.0,
+                y: 0.0,
+                z: -5.0,
+            },
+            direction: Vector {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0,
+            },
+        };
+        let sphere = Sphere::default();
+        let intersections = [Intersection::new(4.0, &sphere)];
+        let comps = intersections[0].prepare_computations(&ray, &intersections);
+        assert_eq!(comps.t, intersections[0].t);
+        assert_eq!(comps.object, intersections[0].object);
+        assert_eq!(
+            comps.point,
+            Point {
+                x: 0.0,
+                y: 0.0,
+                z: -1.0
+            }
+        );
+        assert_eq!(
+            comps.eye,
+            Vector {
+                x: 0.0,
+                y: 0.0,
+                z: -1.0
+            }
+        );
+        assert_eq!(
+            comps.normal,
+            Vector {
+                x: 0.0,
+                y: 0.0,
+                z: -1.0
+            }
+        );
+        assert!(!comps.inside);
+    }
+
+    #[test]
+    fn precompute_state_of_intersection_from_inside() {
+        let ray = Ray {
+            origin: Point::origin(),
+            direction: Vector {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0,
+            },
+        };
+        let sphere = Sphere::default();
+        let intersections = [Intersection::new(1.0, &sphere)];
+        let comps = intersections[0].prepare_computations(&ray, &intersections);
+        assert_eq!(
+            comps.point,
+            Point {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0
+            }
+        );
+        assert_eq!(
+            comps.eye,
+            Vector {
+                x: 0.0,
+                y: 0.0,
+                z: -1.0
+            }
+        );
+        // Normal would have been (0, 0, 1), but is inverted.
+        assert_eq!(
+            comps.normal,
+            Vector {
+                x: 0.0,
+                y: 0.0,
+                z: -1.0
+            }
+        );
+        assert!(comps.inside);
+    }
+
+    #[test]
+    fn hit_should_offset_the_point() {
+        let ray = Ray {
+            origin: Point {
+                x: 0.0,
+                y: 0.0,
+                z: -5.0,
+            },
+            direction: Vector {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0,
+            },
+        };
+        let sphere = Sphere {
+            transform: Transform::new(Matrix4x4::translation(0.0, 0.0, 1.0)).unwrap(),
+            ..Default::default()
+        };
+        let intersections = [Intersection::new(5.0, &sphere)];
+        let comps = intersections[0].prepare_computations(&ray, &intersections);
+        assert!(comps.over_point.z < -epsilon() / 2.0);
+        assert!(comps.point.z > comps.over_point.z);
+    }
+
+    #[test]
+    fn precompute_reflection_vector() {
+        let plane = Plane::default();
+        let value = std::f64::consts::FRAC_1_SQRT_2 as Float;
+        let ray = Ray {
+            origin: Point {
+                x: 0.0,
+                y: 1.0,
+                z: -1.0,
+            },
+            direction: Vector {
+                x: 0.0,
+                y: -value,
+                z: value,
+            },
+        };
+        let intersections = [Intersection::new(Float::sqrt(2.0), &plane)];
+        let comps = intersections[0].prepare_computations(&ray, &intersections);
+        assert!(approx_eq!(comps.reflectv.x, 0.0));
+        assert!(approx_eq!(comps.reflectv.y, value));
+        assert!(approx_eq!(comps.reflectv.z, value));
+    }
+
+    fn glass_sphere(transform: Matrix4x4, refractive_index: Float) -> Sphere {
+        Sphere {
+            transform: Transform::new(transform).unwrap(),
+            material: Material {
+                transparency: 1.0,
+                refractive_index,
+                ..Default::default()
+            },
+        }
+    }
+
+    #[test]
+    fn find_n1_and_n2_at_various_intersections() {
+        let a = glass_sphere(Matrix4x4::scaling(2.0, 2.0, 2.0), 1.5);
+        let b = glass_sphere(Matrix4x4::translation(0.0, 0.0, -0.25), 2.0);
+        let c = glass_sphere(Matrix4x4::translation(0.0, 0.0, 0.25), 2.5);
+        let ray = Ray {
+            origin: Point {
+                x: 0.0,
+                y: 0.0,
+                z: -4.0,
+            },
+            direction: Vector {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0,
+            },
+        };
+        let intersections = [
+            Intersection::new(2.0, &a),
+            Intersection::new(2.75, &b),
+            Intersection::new(3.25, &c),
+            Intersection::new(4.75, &b),
+            Intersection::new(5.25, &c),
+            Intersection::new(6.0, &a),
+        ];
+        let expected = [
+            (1.0, 1.5),
+            (1.5, 2.0),
+            (2.0, 2.5),
+            (2.5, 2.5),
+            (2.5, 1.5),
+            (1.5, 1.0),
+        ];
+        for (intersection, (n1, n2)) in intersections.iter().zip(expected) {
+            let comps = intersection.prepare_computations(&ray, &intersections);
+            assert_eq!(comps.n1, n1);
+            assert_eq!(comps.n2, n2);
+        }
+    }
+
+    #[test]
+    fn find_n1_and_n2_through_glass_mesh() {
+        // Front and back face of a glass slab, hit as different triangles.
+        let face = |z: Float| {
+            MeshTriangle::Flat(Triangle::new(
+                Point { x: 0.0, y: 1.0, z },
+                Point {
+                    x: -1.0,
+                    y: -1.0,
+                    z,
+                },
+                Point { x: 1.0, y: -1.0, z },
+            ))
+        };
+        let mesh = Mesh::new(
+            vec![face(-1.0), face(1.0)],
+            Transform::identity(),
+            glass_sphere(Matrix4x4::identity(), 1.5).material,
+        );
+        let ray = Ray {
+            origin: Point {
+                x: 0.0,
+                y: 0.0,
+                z: -4.0,
+            },
+            direction: Vector {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0,
+            },
+        };
+        let mut intersections = ray.intersect(&mesh);
+        intersections.sort_by(|a, b| a.t.total_cmp(&b.t));
+        assert_eq!(intersections.len(), 2);
+        for (intersection, (n1, n2)) in intersections.iter().zip([(1.0, 1.5), (1.5, 1.0)]) {
+            let comps = intersection.prepare_computations(&ray, &intersections);
+            assert_eq!(comps.n1, n1);
+            assert_eq!(comps.n2, n2);
+        }
+    }
+
+    #[test]
+    fn under_point_is_offset_below_the_surface() {
+        let ray = Ray {
+            origin: Point {
+                x: 0.0,
+                y: 0.0,
+                z: -5.0,
+            },
+            direction: Vector {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0,
+            },
+        };
+        let sphere = glass_sphere(Matrix4x4::translation(0.0, 0.0, 1.0), 1.5);
+        let intersections = [Intersection::new(5.0, &sphere)];
+        let comps = intersections[0].prepare_computations(&ray, &intersections);
+        assert!(comps.under_point.z > epsilon() / 2.0);
+        assert!(comps.point.z < comps.under_point.z);
+    }
+
+    #[test]
+    fn schlick_under_total_internal_reflection() {
+        let sphere = glass_sphere(Matrix4x4::identity(), 1.5);
+        let value = std::f64::consts::FRAC_1_SQRT_2 as Float;
+        let ray = Ray {
+            origin: Point {
+                x: 0.0,
+                y: 0.0,
+                z: value,
+            },
+            direction: Vector {
+                x: 0.0,
+                y: 1.0,
+                z: 0.0,
+            },
+        };
+        let intersections = [
+            Intersection::new(-value, &sphere),
+            Intersection::new(value, &sphere),
+        ];
+        let comps = intersections[1].prepare_computations(&ray, &intersections);
+        assert_eq!(comps.schlick(), 1.0);
+    }
+
+    #[test]
+    fn schlick_with_perpendicular_viewing_angle() {
+        let sphere = glass_sphere(Matrix4x4::identity(), 1.5);
+        let ray = Ray {
+            origin: Point::origin(),
+            direction: Vector {
+                x: 0.0,
+                y: 1.0,
+                z: 0.0,
+            },
+        };
+        let intersections = [
+            Intersection::new(-1.0, &sphere),
+            Intersection::new(1.0, &sphere),
+        ];
+        let comps = intersections[1].prepare_computations(&ray, &intersections);
+        assert!(approx_eq!(comps.schlick(), 0.04));
+    }
+
+    #[test]
+    fn schlick_with_small_angle_and_n2_greater_than_n1() {
+        let sphere = glass_sphere(Matrix4x4::identity(), 1.5);
+        let ray = Ray {
+            origin: Point {
+                x: 0.0,
+                y: 0.99,
+                z: -2.0,
+            },
+            direction: Vector {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0,
+            },
+        };
+        let intersections = [Intersection::new(1.8589, &sphere)];
+        let comps = intersections[0].prepare_computations(&ray, &intersections);
+        assert!(approx_eq!(comps.schlick(), 0.48873, atol = 1e-5));
+    }
+
+    #[test]
+    fn object_point_takes_object_and_parent_transforms_into_account() {
+        let sphere = Sphere {
+            transform: Transform::new(Matrix4x4::scaling(2.0, 2.0, 2.0)).unwrap(),
+            ..Default::default()
+        };
+        let group = Group {
+            transform: Transform::new(Matrix4x4::translation(0.0, 0.0, 5.0)).unwrap(),
+            ..Default::default()
+        };
+        let ray = Ray {
+            origin: Point::origin(),
+            direction: Vector {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0,
+            },
+        };
+        let mut intersection = Intersection::new(3.0, &sphere);
+        intersection.parents.push(&group);
+        let intersections = [intersection];
+        let comps = intersections[0].prepare_computations(&ray, &intersections);
+        assert!(approx_eq!(comps.object_point.x, 0.0));
+        assert!(approx_eq!(comps.object_point.y, 0.0));
+        assert!(approx_eq!(comps.object_point.z, -1.0));
+    }
+
+    // Material with a bump map that does not change the normal.
+    fn flat_bump_material() -> Material {
+        Material {
+            normal_perturbation: Some(Arc::new(BumpMap::new(
+                Arc::new(SolidPattern::new(Color::white())),
+                1.0,
+            ))),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn tangent_frame_is_only_computed_for_normal_perturbations() {
+        let ray = Ray {
+            origin: Point {
+                x: 0.0,
+                y: 0.0,
+                z: -5.0,
+            },
+            direction: Vector {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0,
+            },
+        };
+        let sphere = Sphere::default();
+        let intersections = [Intersection::new(4.0, &sphere)];
+        let comps = intersections[0].prepare_computations(&ray, &intersections);
+        assert!(comps.frame.is_none());
+    }
+
+    #[test]
+    fn tangent_frame_on_sphere() {
+        let sphere = Sphere {
+            material: flat_bump_material(),
+            ..Default::default()
+        };
+        let ray = Ray {
+            origin: Point {
+                x: 0.0,
+                y: 0.0,
+                z: -5.0,
+            },
+            direction: Vector {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0,
+            },
+        };
+        let intersections = [Intersection::new(4.0, &sphere)];
+        let comps = intersections[0].prepare_computations(&ray, &intersections);
+        let frame = comps.frame.unwrap();
+        // Increasing u goes towards +x and increasing v towards +y.
+        assert!(approx_eq!(frame.tangent.x, 1.0));
+        assert!(approx_eq!(frame.tangent.y, 0.0));
+        assert!(approx_eq!(frame.tangent.z, 0.0));
+        assert!(approx_eq!(frame.bitangent.x, 0.0));
+        assert!(approx_eq!(frame.bitangent.y, 1.0));
+        assert!(approx_eq!(frame.bitangent.z, 0.0));
+        assert_eq!(frame.normal, comps.normal);
+    }
+
+    #[test]
+    fn tangent_frame_is_flipped_inside() {
+        let sphere = Sphere {
+            material: flat_bump_material(),
+            ..Default::default()
+        };
+        let ray = Ray {
+            origin: Point::origin(),
+            direction: Vector {
+                x: 0.0,
+                y: 0.0,
+                z: -1.0,
+            },
+        };
+        let intersections = [Intersection::new(1.0, &sphere)];
+        let comps = intersections[0].prepare_computations(&ray, &intersections);
+        let frame = comps.frame.unwrap();
+        assert!(comps.inside);
+        assert!(approx_eq!(comps.normal.z, 1.0));
+        assert_eq!(frame.normal, comps.normal);
+        assert!(approx_eq!(frame.tangent.x, 1.0));
+        assert!(approx_eq!(frame.bitangent.y, -1.0));
+        assert!(approx_eq!(
+            frame.tangent.cross(frame.normal).dot(frame.bitangent),
+            1.0
+        ));
+    }
+
+    #[test]
+    fn tangent_frame_is_transformed_with_object() {
+        let plane = Plane {
+            transform: Transform::new(Matrix4x4::rotation_z(std::f64::consts::FRAC_PI_2 as Float))
+                .unwrap(),
+            material: flat_bump_material(),
+        };
+        let ray = Ray {
+            origin: Point {
+                x: -2.0,
+                y: 0.0,
+                z: 0.0,
+            },
+            direction: Vector {
+                x: 1.0,
+                y: 0.0,
+                z: 0.0,
+            },
+        };
+        let intersections = [Intersection::new(2.0, &plane)];
+        let comps = intersections[0].prepare_computations(&ray, &intersections);
+        let frame = comps.frame.unwrap();
+        assert!(approx_eq!(frame.normal.x, -1.0));
+        assert!(approx_eq!(frame.tangent.y, 1.0));
+        assert!(approx_eq!(frame.bitangent.z, 1.0));
+    }
+
+    #[test]
+    fn normal_map_changes_shading_normal_only() {
+        let map = NormalMap::new(
+            UvMapping::Planar,
+            Arc::new(CheckerTexture {
+                width: 1,
+                height: 1,
+                a: Color {
+                    r: 1.0,
+                    g: 0.5,
+                    b: 1.0,
+                },
+                b: Color::black(),
+            }),
+        );
+        let plane = Plane {
+            material: Material {
+                normal_perturbation: Some(Arc::new(map)),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let ray = Ray {
+            origin: Point {
+                x: 0.25,
+                y: 1.0,
+                z: 0.25,
+            },
+            direction: Vector {
+                x: 0.0,
+                y: -1.0,
+                z: 0.0,
+            },
+        };
+        let intersections = [Intersection::new(1.0, &plane)];
+        let comps = intersections[0].prepare_computations(&ray, &intersections);
+        let value = std::f64::consts::FRAC_1_SQRT_2 as Float;
+        assert!(approx_eq!(comps.normal.x, value));
+        assert!(approx_eq!(comps.normal.y, value));
+        assert!(approx_eq!(comps.normal.z, 0.0));
+        // The tangent frame follows the shading normal.
+        let frame = comps.frame.unwrap();
+        assert!(approx_eq!(frame.tangent.dot(comps.normal), 0.0));
+        assert!(approx_eq!(frame.bitangent.z, 1.0));
+        // Points for secondary rays are still offset along the geometric normal.
+        assert!(approx_eq!(comps.over_point.x, 0.25));
+        assert!(comps.over_point.y > 0.0);
+        assert!(approx_eq!(comps.under_point.x, 0.25));
+        assert!(comps.under_point.y < 0.0);
+        assert!(!comps.inside);
+    }
+
+    #[test]
+    fn pattern_is_evaluated_above_the_surface() {
+        let plane = Plane {
+            material: Material {
+                pattern: Some(Arc::new(CheckerPattern::new(
+                    Color::white(),
+                    Color::black(),
+                ))),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        // The hit point of this ray ends up slightly below the plane due to rounding.
+        let ray = Ray {
+            origin: Point {
+                x: 0.5,
+                y: 0.42,
+                z: 0.5,
+            },
+            direction: Vector {
+                x: 0.0,
+                y: -0.66,
+                z: 0.0,
+            },
+        };
+        let intersections = ray.intersect(&plane);
+        let comps = intersections[0].prepare_computations(&ray, &intersections);
+        assert!(comps.point.y < 0.0);
+        assert!(comps.object_point.y > 0.0);
+        assert_eq!(
+            comps
+                .material
+                .color_at(comps.object_point, comps.texture_coordinates),
+            Color::white()
+        );
+    }
 }
@@ -1,21 +1,59 @@
 //! A simple software raytracer based on the book "The Ray Tracer Challenge".
 #![warn(missing_debug_implementations)]
 
+mod bounding_box;
+mod bvh;
+mod camera;
 mod canvas;
 mod intersection;
 mod material;
+mod noise;
+mod normal_map;
+mod obj_file;
 mod objects;
+mod patterns;
 mod point_light;
 mod primitives;
 mod ray;
+mod texture;
+mod uv_mapping;
+mod world;
 
-pub use canvas::Canvas;
-pub use intersection::Intersection;
+pub use bounding_box::BoundingBox;
+pub use bvh::Bvh;
+pub use camera::Camera;
+pub use canvas::{Canvas, Encoding, PpmError, ToneMapping, TransferFunction};
+pub use intersection::{Computations, Intersection};
 pub use material::Material;
-pub use objects::sphere::Sphere;
+pub use noise::Perlin;
+pub use normal_map::{BumpMap, NormalMap, NormalPerturbation, TangentFrame};
+pub use obj_file::{ObjError, ObjFile, ObjGroup};
+pub use objects::{
+    bvh_group::BvhGroup,
+    cone::Cone,
+    csg::{Csg, CsgOperation},
+    cube::Cube,
+    cylinder::Cylinder,
+    group::Group,
+    mesh::{Mesh, MeshTriangle},
+    plane::Plane,
+    smooth_triangle::SmoothTriangle,
+    sphere::Sphere,
+    triangle::Triangle,
+    Shape,
+};
+pub use patterns::{
+    blend::BlendPattern, checker::CheckerPattern, clouds::CloudsPattern, gradient::GradientPattern,
+    marble::MarblePattern, perturbed::PerturbedPattern, ring::RingPattern, solid::SolidPattern,
+    stripe::StripePattern, texture_map::TextureMapPattern, wood::WoodPattern, Pattern,
+};
 pub use point_light::PointLight;
 pub use primitives::color::Color;
-pub use primitives::float::Float;
+pub use primitives::float::{epsilon, Float};
 pub use primitives::matrix::{Invertible, Matrix, Matrix2x2, Matrix3x3, Matrix4x4};
+pub use primitives::transform::{Transform, TransformError};
 pub use primitives::tuple::{Point, Vector};
 pub use ray::{get_hit, Ray};
+pub use texture::{CheckerTexture, ImageTexture, Texture, TextureFilter, TextureWrap};
+pub use uv_mapping::UvMapping;
+pub use world::World;
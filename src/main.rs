@@ -2,20 +2,88 @@ use raytracer::*;
 use std::error::Error;
 use std::fs::File;
 
-fn main() -> Result<(), Box<dyn Error>> {
-    // Simple program to render a sphere (or rather circle).
-    // Make a square canvas for simplicity.
-    let mut canvas = Canvas::<256, 256>::new();
-    assert_eq!(canvas.width(), canvas.height());
+struct Options {
+    width: usize,
+    height: usize,
+    output: String,
+    // Only applied if given, otherwise the writers clamp the linear values.
+    encoding: Option<Encoding>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            width: 256,
+            height: 256,
+            output: String::from("test.ppm"),
+            encoding: None,
+        }
+    }
+}
 
-    // All rays are shot from the origin.
-    let origin = Point {
-        x: 0.0,
-        y: 0.0,
-        z: -5.0,
+// Parses tone mapping names like `reinhard` or `exposure=1.5`.
+fn parse_tone_mapping(value: &str) -> Result<ToneMapping, Box<dyn Error>> {
+    let (name, parameter) = match value.split_once('=') {
+        Some((name, parameter)) => (name, Some(parameter.parse::<Float>()?)),
+        None => (value, None),
     };
+    match (name, parameter) {
+        ("clamp", None) => Ok(ToneMapping::Clamp),
+        ("reinhard", None) => Ok(ToneMapping::Reinhard),
+        ("extended-reinhard", Some(white)) => Ok(ToneMapping::ExtendedReinhard { white }),
+        ("aces", None) => Ok(ToneMapping::AcesFilmic),
+        ("exposure", Some(exposure)) => Ok(ToneMapping::Exposure { exposure }),
+        _ => Err(format!("invalid tone mapping {}", value).into()),
+    }
+}
 
-    // Simple unit sphere.
+fn parse_transfer(value: &str) -> Result<TransferFunction, Box<dyn Error>> {
+    match value {
+        "linear" => Ok(TransferFunction::Linear),
+        "srgb" => Ok(TransferFunction::Srgb),
+        _ => Err(format!("invalid transfer function {}", value).into()),
+    }
+}
+
+// Parses `--width <pixels>`, `--height <pixels>`, `--output <path>`,
+// `--tone-mapping <name>[=<parameter>]` and `--transfer <linear|srgb>`.
+fn parse_args(args: impl Iterator<Item = String>) -> Result<Options, Box<dyn Error>> {
+    let mut options = Options::default();
+    let mut args = args.skip(1);
+    while let Some(arg) = args.next() {
+        let value = args
+            .next()
+            .ok_or_else(|| format!("missing value for argument {}", arg))?;
+        match arg.as_str() {
+            "--width" => options.width = value.parse()?,
+            "--height" => options.height = value.parse()?,
+            "--output" => options.output = value,
+            "--tone-mapping" => {
+                options
+                    .encoding
+                    .get_or_insert_with(Encoding::default)
+                    .tone_mapping = parse_tone_mapping(&value)?
+            }
+            "--transfer" => {
+                options
+                    .encoding
+                    .get_or_insert_with(Encoding::default)
+                    .transfer = parse_transfer(&value)?
+            }
+            _ => return Err(format!("unknown argument {}", arg).into()),
+        }
+    }
+    if options.width == 0 || options.height == 0 {
+        return Err("width and height must be positive".into());
+    }
+    Ok(options)
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    // Simple program to render a sphere on a floor.
+    let options = parse_args(std::env::args())?;
+
+    // Simple unit sphere lit by a single point light.
     let sphere = Sphere {
         material: Material {
             color: Color {
@@ -27,8 +95,6 @@ fn main() -> Result<(), Box<dyn Error>> {
         },
         ..Default::default()
     };
-
-    // Point light.
     let light = PointLight {
         position: Point {
             x: -10.0,
@@ -37,41 +103,47 @@ fn main() -> Result<(), Box<dyn Error>> {
         },
         intensity: Color::white(),
     };
+    // Floor the sphere is resting on.
+    let floor = Plane {
+        transform: Transform::new(Matrix4x4::translation(0.0, -1.0, 0.0))?,
+        ..Default::default()
+    };
+    let world = World::new(vec![Box::new(sphere), Box::new(floor)], vec![light]);
 
-    // Parameters for the wall.
-    let wall_z: Float = 10.0;
-    let wall_size: Float = 7.0;
-    let wall_half_size: Float = wall_size / 2.0;
-
-    for x in 0..canvas.width() {
-        let frac = (x as Float) / (canvas.width() as Float);
-        let wall_x = frac * wall_size - wall_half_size;
-        for y in 0..canvas.height() {
-            let frac = (y as Float) / (canvas.height() as Float);
-            let wall_y = -(frac * wall_size - wall_half_size);
-            let wall_point = Point {
-                x: wall_x,
-                y: wall_y,
-                z: wall_z,
-            };
-            let ray = Ray {
-                origin,
-                direction: (wall_point - origin).normalize(),
-            };
-            let intersections = ray.intersect(sphere);
-            let hit = get_hit(&intersections);
+    // Camera looking at the sphere from the front.
+    let mut camera = Camera::new(
+        options.width,
+        options.height,
+        std::f64::consts::FRAC_PI_6 as Float,
+    );
+    camera.transform = Transform::new(Matrix4x4::view_transform(
+        Point {
+            x: 0.0,
+            y: 0.0,
+            z: -5.0,
+        },
+        Point::origin(),
+        Vector {
+            x: 0.0,
+            y: 1.0,
+            z: 0.0,
+        },
+    ))?;
 
-            if hit.is_some() {
-                let hit = hit.unwrap();
-                let point = ray.position(hit.t);
-                let normal = hit.object.normal_at(point);
-                let eye = -ray.direction;
-                let color = hit.object.material.lighting(light, point, eye, normal);
-                canvas.set_pixel(x, y, color)?;
-            }
-        }
+    let mut canvas = camera.render_parallel(&world);
+    if let Some(encoding) = options.encoding {
+        canvas = canvas.encode(&encoding);
+    }
+    let mut file = File::create(&options.output)?;
+    match options
+        .output
+        .rsplit_once('.')
+        .map(|(_, extension)| extension)
+    {
+        Some("png") => canvas.write_png(&mut file)?,
+        Some("pfm") => canvas.write_pfm(&mut file)?,
+        Some("hdr") => canvas.write_hdr(&mut file)?,
+        _ => canvas.write_ppm(&mut file)?,
     }
-    let mut file = File::create("test.ppm")?;
-    canvas.write_ppm(&mut file)?;
     Ok(())
 }
@@ -1,31 +1,67 @@
-use crate::{Color, Float, Point, PointLight, Vector};
+use crate::{Color, Computations, Float, NormalPerturbation, Pattern, Point, PointLight};
+use std::sync::Arc;
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Material {
     pub color: Color,
+    /// Replaces `color` if given, evaluated in object space of the shape.
+    pub pattern: Option<Arc<dyn Pattern>>,
+    /// Normal map or bump map changing the normal used for shading.
+    pub normal_perturbation: Option<Arc<dyn NormalPerturbation>>,
     pub ambient: Float,
     pub diffuse: Float,
     pub specular: Float,
     pub shininess: Float,
+    /// Fraction of light that is reflected like in a mirror, from 0 (matte) to 1.
+    pub reflective: Float,
+    /// Fraction of light that passes through the surface, from 0 (opaque) to 1.
+    pub transparency: Float,
+    /// Index of refraction, e.g. 1.0 for vacuum, 1.333 for water and 1.52 for glass.
+    pub refractive_index: Float,
 }
 
 impl Default for Material {
     fn default() -> Self {
         Material {
             color: Color::white(),
+            pattern: None,
+            normal_perturbation: None,
             ambient: 0.1,
             diffuse: 0.9,
             specular: 0.9,
             shininess: 200.0,
+            reflective: 0.0,
+            transparency: 0.0,
+            refractive_index: 1.0,
         }
     }
 }
 
 impl Material {
-    pub fn lighting(&self, light: PointLight, point: Point, eye: Vector, normal: Vector) -> Color {
-        let effective_color = self.color * light.intensity;
-        let lightv = (light.position - point).normalize();
+    /// Color of the surface at a point in object space, with the texture coordinates
+    /// stored on the surface if there are any.
+    pub fn color_at(
+        &self,
+        object_point: Point,
+        texture_coordinates: Option<(Float, Float)>,
+    ) -> Color {
+        match &self.pattern {
+            Some(pattern) => pattern.pattern_at_uv(object_point, texture_coordinates),
+            None => self.color,
+        }
+    }
+
+    /// Phong lighting at the over point of the hit described by `comps`.
+    pub fn lighting(&self, light: PointLight, comps: &Computations, in_shadow: bool) -> Color {
+        let (eye, normal) = (comps.eye, comps.normal);
+        let effective_color =
+            self.color_at(comps.object_point, comps.texture_coordinates) * light.intensity;
+        let lightv = (light.position - comps.over_point).normalize();
         let ambient = effective_color * self.ambient;
+        // Only ambient light reaches points that are in shadow.
+        if in_shadow {
+            return ambient;
+        }
         let light_dot_normal = lightv.dot(normal);
         let mut diffuse = Color::black();
         let mut specular = Color::black();
@@ -45,7 +81,7 @@ impl Material {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::approx_eq;
+    use crate::{approx_eq, Intersection, Ray, Shape, Sphere, StripePattern, Vector};
 
     macro_rules! assert_color_approx_eq {
         ($color1:expr, $color2:expr) => {
@@ -55,19 +91,55 @@ mod tests {
         };
     }
 
+    // Computations for a hit at `position`, as far as lighting is concerned.
+    fn computations(
+        object: &dyn Shape,
+        position: Point,
+        eye: Vector,
+        normal: Vector,
+    ) -> Computations<'_> {
+        let ray = Ray {
+            origin: Point {
+                x: 0.0,
+                y: 0.0,
+                z: -5.0,
+            },
+            direction: Vector {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0,
+            },
+        };
+        let intersections = [Intersection::new(4.0, object)];
+        Computations {
+            point: position,
+            object_point: position,
+            over_point: position,
+            eye,
+            normal,
+            ..intersections[0].prepare_computations(&ray, &intersections)
+        }
+    }
+
     #[test]
     fn create_default_material() {
         let m = Material::default();
         assert_eq!(m.color, Color::white());
+        assert!(m.pattern.is_none());
+        assert!(m.normal_perturbation.is_none());
         assert_eq!(m.ambient, 0.1);
         assert_eq!(m.diffuse, 0.9);
         assert_eq!(m.specular, 0.9);
         assert_eq!(m.shininess, 200.0);
+        assert_eq!(m.reflective, 0.0);
+        assert_eq!(m.transparency, 0.0);
+        assert_eq!(m.refractive_index, 1.0);
     }
 
     #[test]
     fn lighting_with_eye_between_light_and_surface() {
         let m = Material::default();
+        let sphere = Sphere::default();
         let position = Point::origin();
         let eye = Vector {
             x: 0.0,
@@ -88,7 +160,7 @@ mod tests {
             intensity: Color::white(),
         };
         assert_color_approx_eq!(
-            m.lighting(light, position, eye, normal),
+            m.lighting(light, &computations(&sphere, position, eye, normal), false),
             Color {
                 r: 1.9,
                 g: 1.9,
@@ -100,6 +172,7 @@ mod tests {
     #[test]
     fn lighting_with_eye_between_light_and_surface_eye_offset_45_degree() {
         let m = Material::default();
+        let sphere = Sphere::default();
         let position = Point::origin();
         let eye = Vector {
             x: 0.0,
@@ -120,7 +193,7 @@ mod tests {
             intensity: Color::white(),
         };
         assert_color_approx_eq!(
-            m.lighting(light, position, eye, normal),
+            m.lighting(light, &computations(&sphere, position, eye, normal), false),
             Color {
                 r: 1.0,
                 g: 1.0,
@@ -132,6 +205,7 @@ mod tests {
     #[test]
     fn lighting_with_eye_opposite_surface_and_light_offset_45_degree() {
         let m = Material::default();
+        let sphere = Sphere::default();
         let position = Point::origin();
         let eye = Vector {
             x: 0.0,
@@ -152,7 +226,7 @@ mod tests {
             intensity: Color::white(),
         };
         assert_color_approx_eq!(
-            m.lighting(light, position, eye, normal),
+            m.lighting(light, &computations(&sphere, position, eye, normal), false),
             Color {
                 r: 0.7364,
                 g: 0.7364,
@@ -164,6 +238,7 @@ mod tests {
     #[test]
     fn lighting_with_eye_in_the_path_of_the_reflection_vector() {
         let m = Material::default();
+        let sphere = Sphere::default();
         let position = Point::origin();
         let eye = Vector {
             x: 0.0,
@@ -184,7 +259,7 @@ mod tests {
             intensity: Color::white(),
         };
         assert_color_approx_eq!(
-            m.lighting(light, position, eye, normal),
+            m.lighting(light, &computations(&sphere, position, eye, normal), false),
             Color {
                 r: 1.6364,
                 g: 1.6364,
@@ -196,6 +271,7 @@ mod tests {
     #[test]
     fn lighting_with_eye_behind_the_surface() {
         let m = Material::default();
+        let sphere = Sphere::default();
         let position = Point::origin();
         let eye = Vector {
             x: 0.0,
@@ -216,7 +292,40 @@ mod tests {
             intensity: Color::white(),
         };
         assert_color_approx_eq!(
-            m.lighting(light, position, eye, normal),
+            m.lighting(light, &computations(&sphere, position, eye, normal), false),
+            Color {
+                r: 0.1,
+                g: 0.1,
+                b: 0.1,
+            }
+        );
+    }
+
+    #[test]
+    fn lighting_with_surface_in_shadow() {
+        let m = Material::default();
+        let sphere = Sphere::default();
+        let position = Point::origin();
+        let eye = Vector {
+            x: 0.0,
+            y: 0.0,
+            z: -1.0,
+        };
+        let normal = Vector {
+            x: 0.0,
+            y: 0.0,
+            z: -1.0,
+        };
+        let light = PointLight {
+            position: Point {
+                x: 0.0,
+                y: 0.0,
+                z: -10.0,
+            },
+            intensity: Color::white(),
+        };
+        assert_color_approx_eq!(
+            m.lighting(light, &computations(&sphere, position, eye, normal), true),
             Color {
                 r: 0.1,
                 g: 0.1,
@@ -224,4 +333,72 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn lighting_with_pattern() {
+        let sphere = Sphere::default();
+        let m = Material {
+            pattern: Some(Arc::new(StripePattern::new(Color::white(), Color::black()))),
+            ambient: 1.0,
+            diffuse: 0.0,
+            specular: 0.0,
+            ..Default::default()
+        };
+        let eye = Vector {
+            x: 0.0,
+            y: 0.0,
+            z: -1.0,
+        };
+        let normal = Vector {
+            x: 0.0,
+            y: 0.0,
+            z: -1.0,
+        };
+        let light = PointLight {
+            position: Point {
+                x: 0.0,
+                y: 0.0,
+                z: -10.0,
+            },
+            intensity: Color::white(),
+        };
+        let first = Point {
+            x: 0.9,
+            y: 0.0,
+            z: 0.0,
+        };
+        let second = Point {
+            x: 1.1,
+            y: 0.0,
+            z: 0.0,
+        };
+        assert_color_approx_eq!(
+            m.lighting(light, &computations(&sphere, first, eye, normal), false),
+            Color::white()
+        );
+        assert_color_approx_eq!(
+            m.lighting(light, &computations(&sphere, second, eye, normal), false),
+            Color::black()
+        );
+    }
+
+    #[test]
+    fn pattern_is_evaluated_in_object_space() {
+        let m = Material {
+            pattern: Some(Arc::new(StripePattern::new(Color::white(), Color::black()))),
+            ..Default::default()
+        };
+        let world_point = Point {
+            x: 1.5,
+            y: 0.0,
+            z: 0.0,
+        };
+        let object_point = Point {
+            x: 0.75,
+            y: 0.0,
+            z: 0.0,
+        };
+        assert_eq!(m.color_at(world_point, None), Color::black());
+        assert_eq!(m.color_at(object_point, None), Color::white());
+    }
 }
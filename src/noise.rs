@@ -0,0 +1,266 @@
+use crate::{Float, Point, Vector};
+
+// Offsets for sampling independent noise values at the same point.
+const OFFSET_Y: Vector = Vector {
+    x: 31.416,
+    y: -47.853,
+    z: 12.793,
+};
+const OFFSET_Z: Vector = Vector {
+    x: -27.182,
+    y: 18.301,
+    z: -61.803,
+};
+
+/// Gradient noise after Ken Perlin's improved noise.
+///
+/// The permutation table is shuffled using the seed, so the same seed always
+/// produces the same noise.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Perlin {
+    // The permutation of 0..256, repeated once to avoid wrapping the indices.
+    permutation: Vec<u8>,
+}
+
+impl Default for Perlin {
+    fn default() -> Self {
+        Perlin::new(0)
+    }
+}
+
+impl Perlin {
+    pub fn new(seed: u64) -> Self {
+        let mut table: Vec<u8> = (0..=255).collect();
+        // Fisher-Yates shuffle driven by SplitMix64.
+        let mut state = seed;
+        for i in (1..table.len()).rev() {
+            let j = (split_mix_64(&mut state) % (i as u64 + 1)) as usize;
+            table.swap(i, j);
+        }
+        let permutation = table.iter().chain(&table).copied().collect();
+        Perlin { permutation }
+    }
+
+    /// Noise value roughly in [-1, 1], which is 0 at all integer coordinates.
+    pub fn noise(&self, point: Point) -> Float {
+        let p = &self.permutation;
+        let (x, y, z) = (point.x.floor(), point.y.floor(), point.z.floor());
+        // Lattice cell, wrapped into the permutation table.
+        let xi = (x as i64 & 255) as usize;
+        let yi = (y as i64 & 255) as usize;
+        let zi = (z as i64 & 255) as usize;
+        // Position inside the cell.
+        let (x, y, z) = (point.x - x, point.y - y, point.z - z);
+        let (u, v, w) = (fade(x), fade(y), fade(z));
+
+        let a = p[xi] as usize + yi;
+        let aa = p[a] as usize + zi;
+        let ab = p[a + 1] as usize + zi;
+        let b = p[xi + 1] as usize + yi;
+        let ba = p[b] as usize + zi;
+        let bb = p[b + 1] as usize + zi;
+
+        lerp(
+            w,
+            lerp(
+                v,
+                lerp(u, grad(p[aa], x, y, z), grad(p[ba], x - 1.0, y, z)),
+                lerp(
+                    u,
+                    grad(p[ab], x, y - 1.0, z),
+                    grad(p[bb], x - 1.0, y - 1.0, z),
+                ),
+            ),
+            lerp(
+                v,
+                lerp(
+                    u,
+                    grad(p[aa + 1], x, y, z - 1.0),
+                    grad(p[ba + 1], x - 1.0, y, z - 1.0),
+                ),
+                lerp(
+                    u,
+                    grad(p[ab + 1], x, y - 1.0, z - 1.0),
+                    grad(p[bb + 1], x - 1.0, y - 1.0, z - 1.0),
+                ),
+            ),
+        )
+    }
+
+    /// Fractional Brownian motion, the sum of `octaves` noise layers where each
+    /// layer has double the frequency and half the amplitude of the previous one.
+    /// The result is normalized to the range of `noise`.
+    pub fn fbm(&self, point: Point, octaves: usize) -> Float {
+        self.octaves(point, octaves, |noise| noise)
+    }
+
+    /// Like `fbm`, but summing the absolute values, which gives a result in [0, 1]
+    /// with sharp creases where the noise crosses 0.
+    pub fn turbulence(&self, point: Point, octaves: usize) -> Float {
+        self.octaves(point, octaves, Float::abs)
+    }
+
+    /// Vector with independent noise values in every component, used for
+    /// displacing points.
+    pub fn noise_vector(&self, point: Point) -> Vector {
+        Vector {
+            x: self.noise(point),
+            y: self.noise(point + OFFSET_Y),
+            z: self.noise(point + OFFSET_Z),
+        }
+    }
+
+    fn octaves(&self, point: Point, octaves: usize, f: impl Fn(Float) -> Float) -> Float {
+        let mut sum = 0.0;
+        let mut total_amplitude = 0.0;
+        let mut amplitude = 1.0;
+        let mut frequency = 1.0;
+        for _ in 0..octaves {
+            let scaled = Point::origin() + (point - Point::origin()) * frequency;
+            sum += amplitude * f(self.noise(scaled));
+            total_amplitude += amplitude;
+            amplitude *= 0.5;
+            frequency *= 2.0;
+        }
+        if octaves == 0 {
+            0.0
+        } else {
+            sum / total_amplitude
+        }
+    }
+}
+
+fn split_mix_64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e3779b97f4a7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
+// Smoothstep with vanishing first and second derivatives at 0 and 1.
+fn fade(t: Float) -> Float {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+fn lerp(t: Float, a: Float, b: Float) -> Float {
+    a + t * (b - a)
+}
+
+// Dot product of the position with one of 12 gradient directions selected by the hash.
+fn grad(hash: u8, x: Float, y: Float, z: Float) -> Float {
+    let h = hash & 15;
+    let u = if h < 8 { x } else { y };
+    let v = if h < 4 {
+        y
+    } else if h == 12 || h == 14 {
+        x
+    } else {
+        z
+    };
+    (if h & 1 == 0 { u } else { -u }) + (if h & 2 == 0 { v } else { -v })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::approx_eq;
+
+    // Points on a grid that avoids integer coordinates.
+    fn sample_points() -> impl Iterator<Item = Point> {
+        (0..1000).map(|i| Point {
+            x: (i % 10) as Float * 0.37 - 1.7,
+            y: (i / 10 % 10) as Float * 0.53 + 0.1,
+            z: (i / 100) as Float * 0.91 - 3.3,
+        })
+    }
+
+    #[test]
+    fn permutation_contains_every_byte() {
+        let perlin = Perlin::new(42);
+        let mut sorted = perlin.permutation[..256].to_vec();
+        sorted.sort();
+        assert_eq!(sorted, (0..=255).collect::<Vec<u8>>());
+        assert_eq!(perlin.permutation[..256], perlin.permutation[256..]);
+    }
+
+    #[test]
+    fn noise_is_deterministic_for_seed() {
+        let point = Point {
+            x: 1.3,
+            y: -2.7,
+            z: 0.45,
+        };
+        assert_eq!(Perlin::new(7), Perlin::new(7));
+        assert_eq!(Perlin::new(7).noise(point), Perlin::new(7).noise(point));
+        assert_ne!(Perlin::new(7), Perlin::new(8));
+    }
+
+    #[test]
+    fn noise_does_not_change_between_versions() {
+        // Rendered images depend on these values, so changing them is a breaking change.
+        let perlin = Perlin::new(1);
+        let point = Point {
+            x: 0.5,
+            y: 1.25,
+            z: -3.75,
+        };
+        assert!(approx_eq!(perlin.noise(point), 0.138823986, atol = 1e-9));
+        assert!(approx_eq!(perlin.fbm(point, 4), -0.025960541, atol = 1e-9));
+        assert!(approx_eq!(
+            perlin.turbulence(point, 4),
+            0.174039459,
+            atol = 1e-9
+        ));
+    }
+
+    #[test]
+    fn noise_is_zero_at_integer_coordinates() {
+        let perlin = Perlin::new(3);
+        for (x, y, z) in [(0.0, 0.0, 0.0), (1.0, -2.0, 3.0), (-255.0, 256.0, 17.0)] {
+            assert_eq!(perlin.noise(Point { x, y, z }), 0.0);
+        }
+    }
+
+    #[test]
+    fn noise_is_bounded_and_not_constant() {
+        let perlin = Perlin::default();
+        let values: Vec<Float> = sample_points().map(|p| perlin.noise(p)).collect();
+        assert!(values.iter().all(|value| (-1.0..=1.0).contains(value)));
+        assert!(values.iter().any(|&value| value > 0.1));
+        assert!(values.iter().any(|&value| value < -0.1));
+    }
+
+    #[test]
+    fn noise_is_continuous() {
+        let perlin = Perlin::default();
+        let offset = Vector {
+            x: 1e-6,
+            y: 1e-6,
+            z: 1e-6,
+        };
+        for point in sample_points() {
+            let difference = perlin.noise(point) - perlin.noise(point + offset);
+            assert!(difference.abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn fbm_with_single_octave_is_noise() {
+        let perlin = Perlin::default();
+        for point in sample_points() {
+            assert_eq!(perlin.fbm(point, 1), perlin.noise(point));
+            assert_eq!(perlin.turbulence(point, 1), perlin.noise(point).abs());
+        }
+    }
+
+    #[test]
+    fn fbm_and_turbulence_stay_in_range() {
+        let perlin = Perlin::default();
+        for point in sample_points() {
+            assert!((-1.0..=1.0).contains(&perlin.fbm(point, 6)));
+            assert!((0.0..=1.0).contains(&perlin.turbulence(point, 6)));
+        }
+        assert_eq!(perlin.fbm(Point::origin(), 0), 0.0);
+    }
+}
@@ -0,0 +1,298 @@
+use crate::{epsilon, Float, Pattern, Point, Texture, UvMapping, Vector};
+use std::fmt::Debug;
+use std::sync::Arc;
+
+// Step used for the finite differences of bump maps, in object space.
+const BUMP_DELTA: Float = 1e-4;
+
+/// Orthonormal basis on a surface, with the tangent pointing towards increasing
+/// u and the bitangent towards increasing v of the texture coordinates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TangentFrame {
+    pub tangent: Vector,
+    pub bitangent: Vector,
+    pub normal: Vector,
+}
+
+impl TangentFrame {
+    /// Builds the frame around `normal`, only the part of `tangent` perpendicular to
+    /// the normal is used. If there is none, an arbitrary perpendicular direction is
+    /// chosen instead.
+    pub fn new(normal: Vector, tangent: Vector) -> Self {
+        let normal = normal.normalize();
+        let mut tangent = tangent - normal * normal.dot(tangent);
+        if tangent.length() < epsilon() {
+            let axis = if normal.x.abs() < 0.9 {
+                Vector {
+                    x: 1.0,
+                    y: 0.0,
+                    z: 0.0,
+                }
+            } else {
+                Vector {
+                    x: 0.0,
+                    y: 1.0,
+                    z: 0.0,
+                }
+            };
+            tangent = axis - normal * normal.dot(axis);
+        }
+        let tangent = tangent.normalize();
+        TangentFrame {
+            tangent,
+            bitangent: tangent.cross(normal),
+            normal,
+        }
+    }
+
+    /// Converts a vector from tangent space, where z is along the normal.
+    pub fn to_frame(&self, vector: Vector) -> Vector {
+        self.tangent * vector.x + self.bitangent * vector.y + self.normal * vector.z
+    }
+}
+
+/// Changes the normal used for shading a surface without changing its geometry.
+pub trait NormalPerturbation: Debug + Send + Sync {
+    /// Perturbed normal at a point in object space, given the texture coordinates
+    /// stored on the surface and the tangent frame of the geometric normal in
+    /// object space.
+    fn perturb(
+        &self,
+        point: Point,
+        texture_coordinates: Option<(Float, Float)>,
+        frame: &TangentFrame,
+    ) -> Vector;
+}
+
+// Normal perturbations are compared by identity, like patterns.
+impl PartialEq for dyn NormalPerturbation + '_ {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::addr_eq(self, other)
+    }
+}
+
+/// Tangent-space normal map, the red, green and blue channels of the texture
+/// encode the tangent, bitangent and normal components from -1 to 1.
+#[derive(Debug, Clone)]
+pub struct NormalMap {
+    pub mapping: UvMapping,
+    pub texture: Arc<dyn Texture>,
+    /// Scales the tangent components, 0 gives the unperturbed normal.
+    pub strength: Float,
+}
+
+impl NormalMap {
+    pub fn new(mapping: UvMapping, texture: Arc<dyn Texture>) -> Self {
+        NormalMap {
+            mapping,
+            texture,
+            strength: 1.0,
+        }
+    }
+}
+
+impl NormalPerturbation for NormalMap {
+    fn perturb(
+        &self,
+        point: Point,
+        texture_coordinates: Option<(Float, Float)>,
+        frame: &TangentFrame,
+    ) -> Vector {
+        let (u, v) = self.mapping.map_surface(point, texture_coordinates);
+        let color = self.texture.texture_at(u, v);
+        let normal = Vector {
+            x: (2.0 * color.r - 1.0) * self.strength,
+            y: (2.0 * color.g - 1.0) * self.strength,
+            z: 2.0 * color.b - 1.0,
+        };
+        frame.to_frame(normal).normalize()
+    }
+}
+
+/// Bump map using the brightness of a pattern as height above the surface, so
+/// both textures (with `TextureMapPattern`) and noise can be used. The height is
+/// sampled around the point in object space, hence the `Surface` mapping falls
+/// back to `Planar`.
+#[derive(Debug, Clone)]
+pub struct BumpMap {
+    pub height: Arc<dyn Pattern>,
+    /// Height in object space of the brightest color.
+    pub scale: Float,
+}
+
+impl BumpMap {
+    pub fn new(height: Arc<dyn Pattern>, scale: Float) -> Self {
+        BumpMap { height, scale }
+    }
+
+    fn height_at(&self, point: Point) -> Float {
+        let color = self.height.pattern_at(point);
+        self.scale * (color.r + color.g + color.b) / 3.0
+    }
+}
+
+impl NormalPerturbation for BumpMap {
+    fn perturb(
+        &self,
+        point: Point,
+        _texture_coordinates: Option<(Float, Float)>,
+        frame: &TangentFrame,
+    ) -> Vector {
+        // Slopes of the height field along the tangent and the bitangent.
+        let slope = |direction: Vector| {
+            let offset = direction * BUMP_DELTA;
+            (self.height_at(point + offset) - self.height_at(point - offset)) / (2.0 * BUMP_DELTA)
+        };
+        let normal = frame.normal
+            - frame.tangent * slope(frame.tangent)
+            - frame.bitangent * slope(frame.bitangent);
+        normal.normalize()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{approx_eq, CheckerTexture, Color, GradientPattern, SolidPattern};
+
+    macro_rules! assert_vector_approx_eq {
+        ($vector1:expr, $vector2:expr) => {
+            assert!(approx_eq!($vector1.x, $vector2.x));
+            assert!(approx_eq!($vector1.y, $vector2.y));
+            assert!(approx_eq!($vector1.z, $vector2.z));
+        };
+    }
+
+    fn vector(x: Float, y: Float, z: Float) -> Vector {
+        Vector { x, y, z }
+    }
+
+    // Frame of the xz plane, matching the planar mapping.
+    fn plane_frame() -> TangentFrame {
+        TangentFrame::new(vector(0.0, 1.0, 0.0), vector(1.0, 0.0, 0.0))
+    }
+
+    // Texture with a single color everywhere.
+    fn flat_texture(color: Color) -> Arc<dyn Texture> {
+        Arc::new(CheckerTexture {
+            width: 1,
+            height: 1,
+            a: color,
+            b: color,
+        })
+    }
+
+    #[test]
+    fn tangent_frame_is_orthonormal() {
+        let frame = TangentFrame::new(vector(0.0, 0.0, -2.0), vector(1.0, 1.0, 1.0));
+        assert_vector_approx_eq!(frame.normal, vector(0.0, 0.0, -1.0));
+        let value = std::f64::consts::FRAC_1_SQRT_2 as Float;
+        assert_vector_approx_eq!(frame.tangent, vector(value, value, 0.0));
+        assert_vector_approx_eq!(frame.bitangent, vector(-value, value, 0.0));
+    }
+
+    #[test]
+    fn bitangent_follows_v_on_plane() {
+        let frame = plane_frame();
+        assert_vector_approx_eq!(frame.bitangent, vector(0.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn tangent_frame_with_tangent_parallel_to_normal() {
+        let frame = TangentFrame::new(vector(1.0, 0.0, 0.0), vector(3.0, 0.0, 0.0));
+        assert!(approx_eq!(frame.tangent.length(), 1.0));
+        assert!(approx_eq!(frame.tangent.dot(frame.normal), 0.0));
+        assert!(approx_eq!(frame.bitangent.dot(frame.normal), 0.0));
+    }
+
+    #[test]
+    fn convert_vector_to_frame() {
+        let frame = plane_frame();
+        assert_vector_approx_eq!(frame.to_frame(vector(1.0, 2.0, 3.0)), vector(1.0, 3.0, 2.0));
+    }
+
+    #[test]
+    fn flat_normal_map_keeps_normal() {
+        let map = NormalMap::new(
+            UvMapping::Planar,
+            flat_texture(Color {
+                r: 0.5,
+                g: 0.5,
+                b: 1.0,
+            }),
+        );
+        let normal = map.perturb(Point::origin(), None, &plane_frame());
+        assert_vector_approx_eq!(normal, vector(0.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn normal_map_tilts_normal_along_tangent() {
+        let color = Color {
+            r: 1.0,
+            g: 0.5,
+            b: 1.0,
+        };
+        let map = NormalMap::new(UvMapping::Planar, flat_texture(color));
+        let value = std::f64::consts::FRAC_1_SQRT_2 as Float;
+        let normal = map.perturb(Point::origin(), None, &plane_frame());
+        assert_vector_approx_eq!(normal, vector(value, value, 0.0));
+        // Without strength, the normal is not changed.
+        let map = NormalMap {
+            strength: 0.0,
+            ..map
+        };
+        let normal = map.perturb(Point::origin(), None, &plane_frame());
+        assert_vector_approx_eq!(normal, vector(0.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn constant_bump_map_keeps_normal() {
+        let map = BumpMap::new(Arc::new(SolidPattern::new(Color::white())), 1.0);
+        let normal = map.perturb(Point::origin(), None, &plane_frame());
+        assert_vector_approx_eq!(normal, vector(0.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn bump_map_tilts_normal_against_slope() {
+        // Height rising from 0 at x = 0 to 1 at x = 1.
+        let map = BumpMap::new(
+            Arc::new(GradientPattern::new(Color::black(), Color::white())),
+            1.0,
+        );
+        let point = Point {
+            x: 0.5,
+            y: 0.0,
+            z: 0.5,
+        };
+        let normal = map.perturb(point, None, &plane_frame());
+        let value = std::f64::consts::FRAC_1_SQRT_2 as Float;
+        assert!(approx_eq!(normal.x, -value, atol = 1e-6));
+        assert!(approx_eq!(normal.y, value, atol = 1e-6));
+        assert!(approx_eq!(normal.z, 0.0, atol = 1e-6));
+    }
+
+    #[test]
+    fn normal_map_with_surface_mapping() {
+        // Tilted towards the tangent in the lower left square, flat elsewhere.
+        let texture = Arc::new(CheckerTexture {
+            width: 2,
+            height: 2,
+            a: Color {
+                r: 1.0,
+                g: 0.5,
+                b: 1.0,
+            },
+            b: Color {
+                r: 0.5,
+                g: 0.5,
+                b: 1.0,
+            },
+        });
+        let map = NormalMap::new(UvMapping::Surface, texture);
+        let normal = map.perturb(Point::origin(), Some((0.75, 0.25)), &plane_frame());
+        assert_vector_approx_eq!(normal, vector(0.0, 1.0, 0.0));
+        let value = std::f64::consts::FRAC_1_SQRT_2 as Float;
+        let normal = map.perturb(Point::origin(), Some((0.25, 0.25)), &plane_frame());
+        assert_vector_approx_eq!(normal, vector(value, value, 0.0));
+    }
+}
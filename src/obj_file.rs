@@ -0,0 +1,471 @@
+use crate::objects::mesh::{Mesh, MeshTriangle};
+use crate::{Float, Material, Point, SmoothTriangle, Transform, Triangle, Vector};
+use std::io::{self, BufRead, BufReader, Read};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ObjError {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error("invalid OBJ file on line {line}: {message}")]
+    Parse { line: usize, message: String },
+}
+
+/// Named group of triangles, started by a `g` or `o` statement.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ObjGroup {
+    pub name: String,
+    pub triangles: Vec<MeshTriangle>,
+}
+
+/// Contents of a Wavefront OBJ file.
+///
+/// Faces before the first `g` or `o` statement end up in a group with an empty
+/// name. Polygons are triangulated as fans around their first vertex.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct ObjFile {
+    pub vertices: Vec<Point>,
+    pub normals: Vec<Vector>,
+    pub texture_coordinates: Vec<(Float, Float)>,
+    pub groups: Vec<ObjGroup>,
+    /// Numbers (starting at 1) of non-empty lines that were not understood.
+    pub ignored_lines: Vec<usize>,
+}
+
+// Indices of a face vertex, converted to 0-based.
+#[derive(Debug, Clone, Copy)]
+struct FaceVertex {
+    vertex: usize,
+    texture_coordinates: Option<usize>,
+    normal: Option<usize>,
+}
+
+impl ObjFile {
+    /// Reads an OBJ file, supporting `v`, `vn`, `vt`, `f`, `g` and `o` statements.
+    pub fn read_obj<T: Read>(file: &mut T) -> Result<ObjFile, ObjError> {
+        let mut obj = ObjFile::default();
+        let mut group = None;
+        for (index, line) in BufReader::new(file).lines().enumerate() {
+            let line_number = index + 1;
+            let error = |message: String| ObjError::Parse {
+                line: line_number,
+                message,
+            };
+            let line = line?;
+            let mut tokens = line.split_whitespace();
+            let arguments: Vec<&str> = tokens.clone().skip(1).collect();
+            match tokens.next() {
+                None => {}
+                Some(comment) if comment.starts_with('#') => {}
+                Some("v") => {
+                    // The optional weight is ignored.
+                    let c = parse_floats(&arguments, 3, 4).map_err(error)?;
+                    obj.vertices.push(Point {
+                        x: c[0],
+                        y: c[1],
+                        z: c[2],
+                    });
+                }
+                Some("vn") => {
+                    let c = parse_floats(&arguments, 3, 3).map_err(error)?;
+                    obj.normals.push(Vector {
+                        x: c[0],
+                        y: c[1],
+                        z: c[2],
+                    });
+                }
+                Some("vt") => {
+                    let coordinates = parse_floats(&arguments, 1, 3).map_err(error)?;
+                    let v = coordinates.get(1).copied().unwrap_or(0.0);
+                    obj.texture_coordinates.push((coordinates[0], v));
+                }
+                Some("f") => {
+                    let triangles = obj.parse_face(&arguments).map_err(error)?;
+                    let index = *group.get_or_insert_with(|| obj.group_index(""));
+                    obj.groups[index].triangles.extend(triangles);
+                }
+                Some("g") | Some("o") => group = Some(obj.group_index(&arguments.join(" "))),
+                Some(_) => obj.ignored_lines.push(line_number),
+            }
+        }
+        Ok(obj)
+    }
+
+    pub fn group(&self, name: &str) -> Option<&ObjGroup> {
+        self.groups.iter().find(|group| group.name == name)
+    }
+
+    /// Creates a mesh of all triangles in the file.
+    pub fn to_mesh(&self, transform: Transform, material: Material) -> Mesh {
+        let triangles = self
+            .groups
+            .iter()
+            .flat_map(|group| group.triangles.iter().cloned())
+            .collect();
+        Mesh::new(triangles, transform, material)
+    }
+
+    // Returns the index of the group with the given name, creating it if needed.
+    fn group_index(&mut self, name: &str) -> usize {
+        match self.groups.iter().position(|group| group.name == name) {
+            Some(index) => index,
+            None => {
+                self.groups.push(ObjGroup {
+                    name: String::from(name),
+                    triangles: vec![],
+                });
+                self.groups.len() - 1
+            }
+        }
+    }
+
+    fn parse_face(&self, arguments: &[&str]) -> Result<Vec<MeshTriangle>, String> {
+        if arguments.len() < 3 {
+            return Err(format!(
+                "face needs at least 3 vertices, got {}",
+                arguments.len()
+            ));
+        }
+        let vertices = arguments
+            .iter()
+            .map(|argument| self.parse_face_vertex(argument))
+            .collect::<Result<Vec<_>, _>>()?;
+        let first = vertices[0];
+        Ok(vertices[1..]
+            .windows(2)
+            .map(|pair| self.triangle(first, pair[0], pair[1]))
+            .collect())
+    }
+
+    // Parses `v`, `v/vt`, `v//vn` or `v/vt/vn`.
+    fn parse_face_vertex(&self, argument: &str) -> Result<FaceVertex, String> {
+        let mut parts = argument.split('/');
+        let vertex = resolve_index(parts.next(), self.vertices.len(), "vertex")?
+            .ok_or_else(|| format!("missing vertex index in {:?}", argument))?;
+        let texture_coordinates = resolve_index(
+            parts.next(),
+            self.texture_coordinates.len(),
+            "texture coordinate",
+        )?;
+        let normal = resolve_index(parts.next(), self.normals.len(), "normal")?;
+        if parts.next().is_some() {
+            return Err(format!("invalid face vertex {:?}", argument));
+        }
+        Ok(FaceVertex {
+            vertex,
+            texture_coordinates,
+            normal,
+        })
+    }
+
+    // Creates a smooth triangle if all vertices have normals, a flat one otherwise.
+    // Texture coordinates are only kept if all vertices have them.
+    fn triangle(&self, a: FaceVertex, b: FaceVertex, c: FaceVertex) -> MeshTriangle {
+        let (p1, p2, p3) = (
+            self.vertices[a.vertex],
+            self.vertices[b.vertex],
+            self.vertices[c.vertex],
+        );
+        let texture_coordinates = match (
+            a.texture_coordinates,
+            b.texture_coordinates,
+            c.texture_coordinates,
+        ) {
+            (Some(t1), Some(t2), Some(t3)) => Some((
+                self.texture_coordinates[t1],
+                self.texture_coordinates[t2],
+                self.texture_coordinates[t3],
+            )),
+            _ => None,
+        };
+        match (a.normal, b.normal, c.normal) {
+            (Some(n1), Some(n2), Some(n3)) => {
+                let triangle = SmoothTriangle::new(
+                    p1,
+                    p2,
+                    p3,
+                    self.normals[n1],
+                    self.normals[n2],
+                    self.normals[n3],
+                );
+                MeshTriangle::Smooth(match texture_coordinates {
+                    Some((t1, t2, t3)) => triangle.with_texture_coordinates(t1, t2, t3),
+                    None => triangle,
+                })
+            }
+            _ => {
+                let triangle = Triangle::new(p1, p2, p3);
+                MeshTriangle::Flat(match texture_coordinates {
+                    Some((t1, t2, t3)) => triangle.with_texture_coordinates(t1, t2, t3),
+                    None => triangle,
+                })
+            }
+        }
+    }
+}
+
+fn parse_floats(arguments: &[&str], min: usize, max: usize) -> Result<Vec<Float>, String> {
+    if arguments.len() < min || arguments.len() > max {
+        return Err(format!(
+            "expected {} to {} numbers, got {}",
+            min,
+            max,
+            arguments.len()
+        ));
+    }
+    arguments
+        .iter()
+        .map(|argument| {
+            argument
+                .parse::<Float>()
+                .map_err(|_| format!("invalid number {:?}", argument))
+        })
+        .collect()
+}
+
+// Converts a 1-based (or negative, relative to the end) index to a 0-based one.
+// Missing or empty indices are allowed and result in `None`.
+fn resolve_index(index: Option<&str>, count: usize, name: &str) -> Result<Option<usize>, String> {
+    let index = match index {
+        None | Some("") => return Ok(None),
+        Some(index) => index,
+    };
+    let value: isize = index
+        .parse()
+        .map_err(|_| format!("invalid {} index {:?}", name, index))?;
+    let resolved = match value {
+        1.. => value - 1,
+        ..=-1 => count as isize + value,
+        0 => -1,
+    };
+    if resolved < 0 || resolved >= count as isize {
+        return Err(format!(
+            "{} index {} out of range, only {} defined so far",
+            name, value, count
+        ));
+    }
+    Ok(Some(resolved as usize))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Color, Matrix4x4, Shape};
+
+    fn read(content: &str) -> Result<ObjFile, ObjError> {
+        ObjFile::read_obj(&mut content.as_bytes())
+    }
+
+    fn point(x: Float, y: Float, z: Float) -> Point {
+        Point { x, y, z }
+    }
+
+    fn flat(triangle: &MeshTriangle) -> Triangle {
+        match triangle {
+            MeshTriangle::Flat(triangle) => triangle.clone(),
+            MeshTriangle::Smooth(_) => panic!("expected flat triangle"),
+        }
+    }
+
+    fn smooth(triangle: &MeshTriangle) -> SmoothTriangle {
+        match triangle {
+            MeshTriangle::Smooth(triangle) => triangle.clone(),
+            MeshTriangle::Flat(_) => panic!("expected smooth triangle"),
+        }
+    }
+
+    const VERTICES: &str = "\
+v -1 1 0
+v -1 0 0
+v 1 0 0
+v 1 1 0
+v 0 2 0
+";
+
+    #[test]
+    fn ignore_unrecognized_lines() -> Result<(), ObjError> {
+        let obj = read(
+            "There was a young lady named Bright\n\
+             who traveled much faster than light.\n\
+             \n\
+             # She set out one day\n\
+             in a relative way,\n\
+             and came back the previous night.\n",
+        )?;
+        assert_eq!(obj.ignored_lines, vec![1, 2, 5, 6]);
+        assert!(obj.vertices.is_empty());
+        assert!(obj.groups.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn vertex_records() -> Result<(), ObjError> {
+        let obj = read("v -1 1 0\nv -1.0000 0.5000 0.0000\nv 1 0 0 1.0\nv 1 1 0\n")?;
+        assert_eq!(
+            obj.vertices,
+            vec![
+                point(-1.0, 1.0, 0.0),
+                point(-1.0, 0.5, 0.0),
+                point(1.0, 0.0, 0.0),
+                point(1.0, 1.0, 0.0),
+            ]
+        );
+        assert!(obj.ignored_lines.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn vertex_normal_and_texture_records() -> Result<(), ObjError> {
+        let obj = read("vn 0 0 1\nvn 0.707 0 -0.707\nvt 0.5\nvt 0.25 0.75 0\n")?;
+        assert_eq!(
+            obj.normals,
+            vec![
+                Vector {
+                    x: 0.0,
+                    y: 0.0,
+                    z: 1.0
+                },
+                Vector {
+                    x: 0.707,
+                    y: 0.0,
+                    z: -0.707
+                },
+            ]
+        );
+        assert_eq!(obj.texture_coordinates, vec![(0.5, 0.0), (0.25, 0.75)]);
+        Ok(())
+    }
+
+    #[test]
+    fn parse_triangle_faces() -> Result<(), ObjError> {
+        let obj = read(&format!("{}f 1 2 3\nf 1 3 4\n", VERTICES))?;
+        let triangles = &obj.group("").unwrap().triangles;
+        assert_eq!(triangles.len(), 2);
+        let (t1, t2) = (flat(&triangles[0]), flat(&triangles[1]));
+        assert_eq!(t1.p1(), obj.vertices[0]);
+        assert_eq!(t1.p2(), obj.vertices[1]);
+        assert_eq!(t1.p3(), obj.vertices[2]);
+        assert_eq!(t2.p1(), obj.vertices[0]);
+        assert_eq!(t2.p2(), obj.vertices[2]);
+        assert_eq!(t2.p3(), obj.vertices[3]);
+        Ok(())
+    }
+
+    #[test]
+    fn triangulate_polygons() -> Result<(), ObjError> {
+        let obj = read(&format!("{}f 1 2 3 4 5\n", VERTICES))?;
+        let triangles = &obj.group("").unwrap().triangles;
+        assert_eq!(triangles.len(), 3);
+        for (i, triangle) in triangles.iter().enumerate() {
+            let triangle = flat(triangle);
+            assert_eq!(triangle.p1(), obj.vertices[0]);
+            assert_eq!(triangle.p2(), obj.vertices[i + 1]);
+            assert_eq!(triangle.p3(), obj.vertices[i + 2]);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn triangles_in_groups() -> Result<(), ObjError> {
+        let obj = read(&format!(
+            "{}g FirstGroup\nf 1 2 3\no Second Group\nf 1 3 4\ng FirstGroup\nf 1 4 5\n",
+            VERTICES
+        ))?;
+        assert_eq!(obj.groups.len(), 2);
+        let first = &obj.group("FirstGroup").unwrap().triangles;
+        let second = &obj.group("Second Group").unwrap().triangles;
+        assert_eq!(first.len(), 2);
+        assert_eq!(second.len(), 1);
+        assert_eq!(flat(&first[0]).p3(), obj.vertices[2]);
+        assert_eq!(flat(&second[0]).p3(), obj.vertices[3]);
+        assert_eq!(flat(&first[1]).p3(), obj.vertices[4]);
+        Ok(())
+    }
+
+    #[test]
+    fn faces_with_normals() -> Result<(), ObjError> {
+        let obj = read(
+            "v 0 1 0\nv -1 0 0\nv 1 0 0\n\
+             vn -1 0 0\nvn 1 0 0\nvn 0 1 0\nvt 0 0\n\
+             f 1//3 2//1 3//2\nf 1/1/3 2/1/1 3/1/2\nf 1/1 2/1 3/1\nf -3 -2 -1\n",
+        )?;
+        let triangles = &obj.group("").unwrap().triangles;
+        assert_eq!(triangles.len(), 4);
+        for triangle in &triangles[..2] {
+            let triangle = smooth(triangle);
+            assert_eq!(triangle.p1(), obj.vertices[0]);
+            assert_eq!(triangle.p2(), obj.vertices[1]);
+            assert_eq!(triangle.p3(), obj.vertices[2]);
+            assert_eq!(triangle.n1(), obj.normals[2]);
+            assert_eq!(triangle.n2(), obj.normals[0]);
+            assert_eq!(triangle.n3(), obj.normals[1]);
+        }
+        // Without normals, and with negative (relative) indices.
+        for triangle in &triangles[2..] {
+            let triangle = flat(triangle);
+            assert_eq!(triangle.p1(), obj.vertices[0]);
+            assert_eq!(triangle.p3(), obj.vertices[2]);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn faces_with_texture_coordinates() -> Result<(), ObjError> {
+        let obj = read(
+            "v 0 1 0\nv -1 0 0\nv 1 0 0\nvn 0 0 -1\n\
+             vt 0.5 1\nvt 0 0\nvt 1 0\n\
+             f 1/1 2/2 3/3\nf 1/1/1 2/2/1 3/3/1\nf 1 2/2 3/3\n",
+        )?;
+        let triangles = &obj.group("").unwrap().triangles;
+        let expected = Some([(0.5, 1.0), (0.0, 0.0), (1.0, 0.0)]);
+        assert_eq!(flat(&triangles[0]).texture_coordinates(), expected);
+        assert_eq!(smooth(&triangles[1]).texture_coordinates(), expected);
+        // Only kept if every vertex has them.
+        assert_eq!(flat(&triangles[2]).texture_coordinates(), None);
+        Ok(())
+    }
+
+    #[test]
+    fn errors_report_line_numbers() {
+        let cases = [
+            ("v 1 2\n", 1),
+            ("v 1 2 3\nv 1 x 3\n", 2),
+            ("# comment\n\nvn 1 2 3 4\n", 3),
+            ("v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2\n", 4),
+            ("v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 4\n", 4),
+            ("v 0 0 0\nv 1 0 0\nv 0 1 0\nf 0 1 2\n", 4),
+            ("v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1//1 2 3\n", 4),
+            ("v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1/1/1/1 2 3\n", 4),
+        ];
+        for (content, expected_line) in cases {
+            match read(content) {
+                Err(ObjError::Parse { line, .. }) => assert_eq!(line, expected_line),
+                result => panic!("expected parse error for {:?}, got {:?}", content, result),
+            }
+        }
+    }
+
+    #[test]
+    fn error_message_contains_line_number() {
+        let e = read("v 1 2 3\nv a b c\n").unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "invalid OBJ file on line 2: invalid number \"a\""
+        );
+    }
+
+    #[test]
+    fn convert_to_mesh() -> Result<(), ObjError> {
+        let obj = read(&format!("{}f 1 2 3\ng Other\nf 1 3 4 5\n", VERTICES))?;
+        let transform = Transform::new(Matrix4x4::scaling(2.0, 2.0, 2.0)).unwrap();
+        let material = Material {
+            color: Color::red(),
+            ..Default::default()
+        };
+        let mesh = obj.to_mesh(transform, material.clone());
+        assert_eq!(mesh.triangles().len(), 3);
+        assert_eq!(mesh.transform(), &transform);
+        assert_eq!(mesh.material(), &material);
+        Ok(())
+    }
+}
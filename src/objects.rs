@@ -1 +1,322 @@
+pub mod bvh_group;
+pub mod cone;
+pub mod csg;
+pub mod cube;
+pub mod cylinder;
+pub mod group;
+pub mod mesh;
+pub mod plane;
+pub mod smooth_triangle;
 pub mod sphere;
+pub mod triangle;
+
+use crate::{epsilon, BoundingBox, Float, Intersection, Material, Point, Ray, Transform, Vector};
+use std::fmt::Debug;
+
+/// Common interface of everything that can be intersected by a `Ray`.
+///
+/// Implementors only have to deal with their own object space, the conversion
+/// from and to world space is done using `transform`. Shapes are shared between
+/// render threads, hence they have to be `Send + Sync`.
+pub trait Shape: Debug + Send + Sync {
+    fn transform(&self) -> &Transform;
+
+    fn material(&self) -> &Material;
+
+    fn local_intersect(&self, ray: &Ray) -> Vec<Intersection<'_>>;
+
+    fn local_normal_at(&self, point: Point) -> Vector;
+
+    /// Bounding box in object space.
+    fn local_bounds(&self) -> BoundingBox;
+
+    /// Bounding box in the space of the parent (or world space).
+    fn bounds(&self) -> BoundingBox {
+        self.local_bounds().transform(self.transform().matrix())
+    }
+
+    /// Normal at a point given the barycentric coordinates of the hit. Only shapes
+    /// interpolating their normals, like `SmoothTriangle`, need to override this.
+    fn local_normal_at_uv(&self, point: Point, _u: Float, _v: Float) -> Vector {
+        self.local_normal_at(point)
+    }
+
+    /// Texture coordinates stored on the surface, like the `vt` coordinates of OBJ
+    /// files, given the barycentric coordinates of the hit.
+    fn texture_coordinates_at(&self, _u: Float, _v: Float) -> Option<(Float, Float)> {
+        None
+    }
+
+    /// Material used for children without their own, like the triangles of a `Mesh`.
+    fn shared_material(&self) -> Option<&Material> {
+        None
+    }
+
+    /// Direction of increasing u on the surface in object space, used for tangent
+    /// frames. Follows the angle around the y axis like the spherical mapping.
+    fn local_tangent_at(&self, point: Point) -> Vector {
+        tangent_around_y(point)
+    }
+
+    /// Normal in the space of the parent (or world space). Groups containing the
+    /// shape are not known here, `Intersection::normal_at` takes them into account.
+    fn normal_at(&self, world_point: Point) -> Vector {
+        self.normal_at_uv(world_point, 0.0, 0.0)
+    }
+
+    fn normal_at_uv(&self, world_point: Point, u: Float, v: Float) -> Vector {
+        let object_point = self.point_to_object_space(world_point);
+        self.normal_from_object_space(self.local_normal_at_uv(object_point, u, v))
+    }
+
+    /// Converts a point from the space of the parent (or world space) into object space.
+    fn point_to_object_space(&self, point: Point) -> Point {
+        self.transform()
+            .inverse()
+            .matmul(point)
+            .try_into()
+            .expect("should be convertible to Point")
+    }
+
+    /// Converts a direction from object space into the space of the parent (or world space).
+    fn vector_from_object_space(&self, vector: Vector) -> Vector {
+        self.transform()
+            .matrix()
+            .matmul(vector)
+            .try_into()
+            .expect("should be convertible to Vector")
+    }
+
+    /// Converts a normal from object space into the space of the parent (or world space).
+    fn normal_from_object_space(&self, normal: Vector) -> Vector {
+        let mut normal = self.transform().inverse_transpose().matmul(normal);
+        normal.data[3][0] = 0.0;
+        Vector::try_from(normal)
+            .expect("should be convertible to Vector")
+            .normalize()
+    }
+}
+
+// Shapes are compared by identity, two intersections refer to the same object
+// only if they point to the same shape.
+impl PartialEq for dyn Shape + '_ {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::addr_eq(self, other)
+    }
+}
+
+// Direction of increasing angle around the y axis as used by the spherical and
+// cylindrical mappings, or the x axis on the y axis itself.
+fn tangent_around_y(point: Point) -> Vector {
+    let around_y = Vector {
+        x: -point.z,
+        y: 0.0,
+        z: point.x,
+    };
+    if around_y.length() < epsilon() {
+        Vector {
+            x: 1.0,
+            y: 0.0,
+            z: 0.0,
+        }
+    } else {
+        around_y
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{approx_eq, Matrix4x4};
+    use std::sync::Mutex;
+
+    #[derive(Debug)]
+    struct TestShape {
+        transform: Transform,
+        material: Material,
+        saved_ray: Mutex<Option<Ray>>,
+    }
+
+    impl Default for TestShape {
+        fn default() -> Self {
+            TestShape {
+                transform: Transform::identity(),
+                material: Material::default(),
+                saved_ray: Mutex::new(None),
+            }
+        }
+    }
+
+    impl Shape for TestShape {
+        fn transform(&self) -> &Transform {
+            &self.transform
+        }
+
+        fn material(&self) -> &Material {
+            &self.material
+        }
+
+        fn local_intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+            *self.saved_ray.lock().unwrap() = Some(*ray);
+            vec![]
+        }
+
+        fn local_normal_at(&self, point: Point) -> Vector {
+            point - Point::origin()
+        }
+
+        fn local_bounds(&self) -> BoundingBox {
+            BoundingBox {
+                min: Point {
+                    x: -1.0,
+                    y: -1.0,
+                    z: -1.0,
+                },
+                max: Point {
+                    x: 1.0,
+                    y: 1.0,
+                    z: 1.0,
+                },
+            }
+        }
+    }
+
+    #[test]
+    fn intersecting_scaled_shape_with_ray() {
+        let ray = Ray {
+            origin: Point {
+                x: 0.0,
+                y: 0.0,
+                z: -5.0,
+            },
+            direction: Vector {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0,
+            },
+        };
+        let shape = TestShape {
+            transform: Transform::new(Matrix4x4::scaling(2.0, 2.0, 2.0)).unwrap(),
+            ..Default::default()
+        };
+        ray.intersect(&shape);
+        let saved_ray = shape.saved_ray.lock().unwrap().unwrap();
+        assert_eq!(
+            saved_ray.origin,
+            Point {
+                x: 0.0,
+                y: 0.0,
+                z: -2.5
+            }
+        );
+        assert_eq!(
+            saved_ray.direction,
+            Vector {
+                x: 0.0,
+                y: 0.0,
+                z: 0.5
+            }
+        );
+    }
+
+    #[test]
+    fn intersecting_translated_shape_with_ray() {
+        let ray = Ray {
+            origin: Point {
+                x: 0.0,
+                y: 0.0,
+                z: -5.0,
+            },
+            direction: Vector {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0,
+            },
+        };
+        let shape = TestShape {
+            transform: Transform::new(Matrix4x4::translation(5.0, 0.0, 0.0)).unwrap(),
+            ..Default::default()
+        };
+        ray.intersect(&shape);
+        let saved_ray = shape.saved_ray.lock().unwrap().unwrap();
+        assert_eq!(
+            saved_ray.origin,
+            Point {
+                x: -5.0,
+                y: 0.0,
+                z: -5.0
+            }
+        );
+        assert_eq!(
+            saved_ray.direction,
+            Vector {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0
+            }
+        );
+    }
+
+    #[test]
+    fn normal_on_a_translated_shape() {
+        let shape = TestShape {
+            transform: Transform::new(Matrix4x4::translation(0.0, 1.0, 0.0)).unwrap(),
+            ..Default::default()
+        };
+        let normal = shape.normal_at(Point {
+            x: 0.0,
+            y: 1.0 + std::f64::consts::FRAC_1_SQRT_2 as Float,
+            z: -std::f64::consts::FRAC_1_SQRT_2 as Float,
+        });
+        assert!(approx_eq!(normal.x, 0.0));
+        assert!(approx_eq!(
+            normal.y,
+            std::f64::consts::FRAC_1_SQRT_2 as Float
+        ));
+        assert!(approx_eq!(
+            normal.z,
+            -std::f64::consts::FRAC_1_SQRT_2 as Float
+        ));
+    }
+
+    #[test]
+    fn shapes_are_compared_by_identity() {
+        let a = TestShape::default();
+        let b = TestShape::default();
+        let a_ref: &dyn Shape = &a;
+        let b_ref: &dyn Shape = &b;
+        assert_eq!(a_ref, a_ref);
+        assert_ne!(a_ref, b_ref);
+    }
+
+    #[test]
+    fn default_tangent_goes_around_y_axis() {
+        let shape = TestShape::default();
+        let point = |x, y, z| Point { x, y, z };
+        assert_eq!(
+            shape.local_tangent_at(point(0.0, 0.0, -1.0)),
+            Vector {
+                x: 1.0,
+                y: 0.0,
+                z: 0.0
+            }
+        );
+        assert_eq!(
+            shape.local_tangent_at(point(1.0, 0.5, 0.0)),
+            Vector {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0
+            }
+        );
+        // No direction around the axis on the axis itself.
+        assert_eq!(
+            shape.local_tangent_at(point(0.0, 1.0, 0.0)),
+            Vector {
+                x: 1.0,
+                y: 0.0,
+                z: 0.0
+            }
+        );
+    }
+}
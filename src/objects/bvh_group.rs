@@ -0,0 +1,235 @@
+use crate::{BoundingBox, Bvh, Intersection, Material, Point, Ray, Shape, Transform, Vector};
+
+/// Group whose children are stored in a bounding volume hierarchy.
+///
+/// Behaves like a `Group`, but rays are only tested against children whose bounds
+/// they hit. As the hierarchy is built on construction, the children cannot be
+/// changed afterwards.
+#[derive(Debug)]
+pub struct BvhGroup {
+    pub transform: Transform,
+    /// Never used for shading, intersections refer to the children.
+    pub material: Material,
+    children: Vec<Box<dyn Shape>>,
+    bvh: Bvh,
+}
+
+impl BvhGroup {
+    pub fn new(children: Vec<Box<dyn Shape>>) -> Self {
+        let bounds: Vec<BoundingBox> = children.iter().map(|child| child.bounds()).collect();
+        BvhGroup {
+            transform: Transform::identity(),
+            material: Material::default(),
+            children,
+            bvh: Bvh::new(&bounds),
+        }
+    }
+
+    pub fn children(&self) -> &[Box<dyn Shape>] {
+        &self.children
+    }
+}
+
+impl Shape for BvhGroup {
+    fn transform(&self) -> &Transform {
+        &self.transform
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+
+    fn local_intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        let mut intersections = vec![];
+        self.bvh.traverse(ray, |i| {
+            intersections.extend(ray.intersect(self.children[i].as_ref()))
+        });
+        for intersection in &mut intersections {
+            intersection.parents.push(self);
+        }
+        intersections.sort_by(|a, b| a.t.total_cmp(&b.t));
+        intersections
+    }
+
+    // Like for `Group`, normals come from the intersected child, so this is only a
+    // guess pointing away from the origin.
+    fn local_normal_at(&self, point: Point) -> Vector {
+        point - Point::origin()
+    }
+
+    fn local_bounds(&self) -> BoundingBox {
+        self.children
+            .iter()
+            .fold(BoundingBox::empty(), |bounds, child| {
+                bounds.merge(&child.bounds())
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Cube, Float, Group, Matrix4x4, Plane, Sphere, Triangle};
+
+    // Simple linear congruential generator, so that the scenes are reproducible.
+    struct Random(u64);
+
+    impl Random {
+        fn next(&mut self) -> Float {
+            self.0 = self
+                .0
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (self.0 >> 11) as Float / (1u64 << 53) as Float
+        }
+
+        fn range(&mut self, min: Float, max: Float) -> Float {
+            min + (max - min) * self.next()
+        }
+
+        fn point(&mut self, size: Float) -> Point {
+            Point {
+                x: self.range(-size, size),
+                y: self.range(-size, size),
+                z: self.range(-size, size),
+            }
+        }
+    }
+
+    // Creates the same pseudo-random mix of shapes for the same seed.
+    fn random_shapes(seed: u64, count: usize) -> Vec<Box<dyn Shape>> {
+        let mut random = Random(seed);
+        (0..count)
+            .map(|i| -> Box<dyn Shape> {
+                let p = random.point(20.0);
+                let scale = random.range(0.2, 1.5);
+                let transform = Transform::new(
+                    Matrix4x4::translation(p.x, p.y, p.z)
+                        .matmul(Matrix4x4::rotation_y(random.range(0.0, 3.0)))
+                        .matmul(Matrix4x4::scaling(scale, scale, scale)),
+                )
+                .unwrap();
+                match i % 3 {
+                    0 => Box::new(Sphere {
+                        transform,
+                        ..Default::default()
+                    }),
+                    1 => Box::new(Cube {
+                        transform,
+                        ..Default::default()
+                    }),
+                    _ => Box::new(Triangle::new(
+                        random.point(20.0),
+                        random.point(20.0),
+                        random.point(20.0),
+                    )),
+                }
+            })
+            .collect()
+    }
+
+    // Intersections as `t` and the index of the child that was hit.
+    fn hits(intersections: &[Intersection], children: &[Box<dyn Shape>]) -> Vec<(Float, usize)> {
+        intersections
+            .iter()
+            .map(|i| {
+                let index = children
+                    .iter()
+                    .position(|child| child.as_ref() == i.object)
+                    .expect("object should be a child");
+                (i.t, index)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn bounds_of_children_respect_transforms() {
+        let group = BvhGroup::new(vec![
+            Box::new(Sphere {
+                transform: Transform::new(
+                    Matrix4x4::translation(2.0, 5.0, -3.0)
+                        .matmul(Matrix4x4::scaling(2.0, 2.0, 2.0)),
+                )
+                .unwrap(),
+                ..Default::default()
+            }),
+            Box::new(Cube {
+                transform: Transform::new(Matrix4x4::translation(-4.0, -1.0, 4.0)).unwrap(),
+                ..Default::default()
+            }),
+        ]);
+        let bounds = group.local_bounds();
+        assert_eq!(
+            bounds.min,
+            Point {
+                x: -5.0,
+                y: -2.0,
+                z: -5.0
+            }
+        );
+        assert_eq!(
+            bounds.max,
+            Point {
+                x: 4.0,
+                y: 7.0,
+                z: 5.0
+            }
+        );
+    }
+
+    #[test]
+    fn bvh_group_gives_same_hits_as_brute_force() {
+        let group = Group {
+            children: random_shapes(42, 300),
+            ..Default::default()
+        };
+        let bvh_group = BvhGroup::new(random_shapes(42, 300));
+        let mut random = Random(7);
+        let mut total_hits = 0;
+        for _ in 0..500 {
+            // Rays starting inside and outside of the scene.
+            let ray = Ray {
+                origin: random.point(30.0),
+                direction: random.point(1.0) - Point::origin(),
+            };
+            let expected = hits(&group.local_intersect(&ray), &group.children);
+            let actual = hits(&bvh_group.local_intersect(&ray), bvh_group.children());
+            assert_eq!(actual, expected);
+            total_hits += actual.len();
+        }
+        // Make sure the test is not trivially passing.
+        assert!(total_hits > 500);
+    }
+
+    #[test]
+    fn transformed_bvh_group_with_unbounded_child() {
+        let children = || -> Vec<Box<dyn Shape>> {
+            let mut children = random_shapes(3, 50);
+            children.push(Box::new(Plane::default()));
+            children
+        };
+        let transform =
+            Transform::new(Matrix4x4::rotation_x(0.5).matmul(Matrix4x4::scaling(1.0, 2.0, 1.0)))
+                .unwrap();
+        let group = Group {
+            transform,
+            children: children(),
+            ..Default::default()
+        };
+        let mut bvh_group = BvhGroup::new(children());
+        bvh_group.transform = transform;
+        let mut random = Random(11);
+        for _ in 0..200 {
+            let ray = Ray {
+                origin: random.point(30.0),
+                direction: random.point(1.0) - Point::origin(),
+            };
+            let expected = hits(&ray.intersect(&group), &group.children);
+            let intersections = ray.intersect(&bvh_group);
+            assert_eq!(hits(&intersections, bvh_group.children()), expected);
+            for intersection in &intersections {
+                assert_eq!(intersection.parents, vec![&bvh_group as &dyn Shape]);
+            }
+        }
+    }
+}
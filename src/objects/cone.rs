@@ -0,0 +1,342 @@
+use super::cylinder::check_cap;
+use crate::{
+    epsilon, BoundingBox, Float, Intersection, Material, Point, Ray, Shape, Transform, Vector,
+};
+
+/// Double cone around the y axis in object space, its radius at height y is |y|.
+///
+/// It can be truncated to `minimum < y < maximum` (both exclusive), in which case
+/// `closed` determines whether the ends are capped.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Cone {
+    pub transform: Transform,
+    pub material: Material,
+    pub minimum: Float,
+    pub maximum: Float,
+    pub closed: bool,
+}
+
+impl Default for Cone {
+    fn default() -> Self {
+        Cone {
+            transform: Transform::identity(),
+            material: Material::default(),
+            minimum: -Float::INFINITY,
+            maximum: Float::INFINITY,
+            closed: false,
+        }
+    }
+}
+
+impl Cone {
+    fn intersect_caps<'a>(&'a self, ray: &Ray, intersections: &mut Vec<Intersection<'a>>) {
+        // Caps only matter for closed cones, and rays parallel to them miss.
+        if !self.closed || ray.direction.y.abs() < epsilon() {
+            return;
+        }
+        // Without a bound, the cap would have an infinite radius at infinity.
+        for y in [self.minimum, self.maximum] {
+            let t = (y - ray.origin.y) / ray.direction.y;
+            if y.is_finite() && check_cap(ray, t, y.abs()) {
+                intersections.push(Intersection::new(t, self));
+            }
+        }
+    }
+
+    fn push_if_in_range<'a>(
+        &'a self,
+        ray: &Ray,
+        t: Float,
+        intersections: &mut Vec<Intersection<'a>>,
+    ) {
+        let y = ray.origin.y + t * ray.direction.y;
+        if self.minimum < y && y < self.maximum {
+            intersections.push(Intersection::new(t, self));
+        }
+    }
+}
+
+impl Shape for Cone {
+    fn transform(&self) -> &Transform {
+        &self.transform
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+
+    fn local_intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        let (o, d) = (ray.origin, ray.direction);
+        let a = d.x * d.x - d.y * d.y + d.z * d.z;
+        let b = 2.0 * (o.x * d.x - o.y * d.y + o.z * d.z);
+        let c = o.x * o.x - o.y * o.y + o.z * o.z;
+        let mut intersections = vec![];
+        if a.abs() < epsilon() {
+            // The ray is parallel to one of the halves and hits the other one once.
+            if b.abs() >= epsilon() {
+                self.push_if_in_range(ray, -c / (2.0 * b), &mut intersections);
+            }
+        } else {
+            let discriminant = b * b - 4.0 * a * c;
+            if discriminant >= 0.0 {
+                let sqrt = Float::sqrt(discriminant);
+                let (t0, t1) = ((-b - sqrt) / (2.0 * a), (-b + sqrt) / (2.0 * a));
+                let (t0, t1) = if t0 > t1 { (t1, t0) } else { (t0, t1) };
+                self.push_if_in_range(ray, t0, &mut intersections);
+                self.push_if_in_range(ray, t1, &mut intersections);
+            }
+        }
+        self.intersect_caps(ray, &mut intersections);
+        intersections
+    }
+
+    fn local_bounds(&self) -> BoundingBox {
+        let radius = self.minimum.abs().max(self.maximum.abs());
+        BoundingBox {
+            min: Point {
+                x: -radius,
+                y: self.minimum,
+                z: -radius,
+            },
+            max: Point {
+                x: radius,
+                y: self.maximum,
+                z: radius,
+            },
+        }
+    }
+
+    fn local_normal_at(&self, point: Point) -> Vector {
+        let distance = point.x * point.x + point.z * point.z;
+        if distance < self.maximum * self.maximum && point.y >= self.maximum - epsilon() {
+            Vector {
+                x: 0.0,
+                y: 1.0,
+                z: 0.0,
+            }
+        } else if distance < self.minimum * self.minimum && point.y <= self.minimum + epsilon() {
+            Vector {
+                x: 0.0,
+                y: -1.0,
+                z: 0.0,
+            }
+        } else {
+            let y = Float::sqrt(distance);
+            Vector {
+                x: point.x,
+                y: if point.y > 0.0 { -y } else { y },
+                z: point.z,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{approx_eq, Matrix4x4, UvMapping};
+
+    fn ray(origin: (Float, Float, Float), direction: (Float, Float, Float)) -> Ray {
+        Ray {
+            origin: Point {
+                x: origin.0,
+                y: origin.1,
+                z: origin.2,
+            },
+            direction: Vector {
+                x: direction.0,
+                y: direction.1,
+                z: direction.2,
+            }
+            .normalize(),
+        }
+    }
+
+    #[test]
+    fn cone_default_constructor() {
+        let cone = Cone::default();
+        assert_eq!(cone.transform, Transform::identity());
+        assert_eq!(cone.material, Material::default());
+        assert_eq!(cone.minimum, -Float::INFINITY);
+        assert_eq!(cone.maximum, Float::INFINITY);
+        assert!(!cone.closed);
+    }
+
+    #[test]
+    fn ray_intersects_cone() {
+        let cone = Cone::default();
+        let cases = [
+            ((0.0, 0.0, -5.0), (0.0, 0.0, 1.0), 5.0, 5.0),
+            ((0.0, 0.0, -5.0), (1.0, 1.0, 1.0), 8.66025, 8.66025),
+            ((1.0, 1.0, -5.0), (-0.5, -1.0, 1.0), 4.55006, 49.44994),
+        ];
+        for (origin, direction, t0, t1) in cases {
+            let intersections = cone.local_intersect(&ray(origin, direction));
+            assert_eq!(intersections.len(), 2);
+            assert!(approx_eq!(intersections[0].t, t0, atol = 1e-4));
+            assert!(approx_eq!(intersections[1].t, t1, atol = 1e-4));
+        }
+    }
+
+    #[test]
+    fn ray_parallel_to_one_half_of_cone() {
+        let cone = Cone::default();
+        let intersections = cone.local_intersect(&ray((0.0, 0.0, -1.0), (0.0, 1.0, 1.0)));
+        assert_eq!(intersections.len(), 1);
+        assert!(approx_eq!(intersections[0].t, 0.35355, atol = 1e-5));
+    }
+
+    #[test]
+    fn intersect_caps_of_closed_cone() {
+        let cone = Cone {
+            minimum: -0.5,
+            maximum: 0.5,
+            closed: true,
+            ..Default::default()
+        };
+        let cases = [
+            ((0.0, 0.0, -5.0), (0.0, 1.0, 0.0), 0),
+            ((0.0, 0.0, -0.25), (0.0, 1.0, 1.0), 2),
+            ((0.0, 0.0, -0.25), (0.0, 1.0, 0.0), 4),
+        ];
+        for (origin, direction, count) in cases {
+            assert_eq!(cone.local_intersect(&ray(origin, direction)).len(), count);
+        }
+    }
+
+    #[test]
+    fn closed_cone_without_bounds_has_no_caps() {
+        let open = Cone::default();
+        let closed = Cone {
+            closed: true,
+            ..Default::default()
+        };
+        let ray = ray((0.0, 0.0, -5.0), (1.0, 1.0, 1.0));
+        let intersections = closed.local_intersect(&ray);
+        assert_eq!(intersections.len(), open.local_intersect(&ray).len());
+        assert!(intersections.iter().all(|i| i.t.is_finite()));
+    }
+
+    #[test]
+    fn normal_on_cone() {
+        let cone = Cone::default();
+        let sqrt2 = Float::sqrt(2.0);
+        let cases = [
+            ((0.0, 0.0, 0.0), (0.0, 0.0, 0.0)),
+            ((1.0, 1.0, 1.0), (1.0, -sqrt2, 1.0)),
+            ((-1.0, -1.0, 0.0), (-1.0, 1.0, 0.0)),
+        ];
+        for ((x, y, z), (nx, ny, nz)) in cases {
+            assert_eq!(
+                cone.local_normal_at(Point { x, y, z }),
+                Vector {
+                    x: nx,
+                    y: ny,
+                    z: nz
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn normal_on_cone_caps() {
+        let cone = Cone {
+            minimum: -1.0,
+            maximum: 2.0,
+            closed: true,
+            ..Default::default()
+        };
+        let up = Vector {
+            x: 0.0,
+            y: 1.0,
+            z: 0.0,
+        };
+        assert_eq!(
+            cone.local_normal_at(Point {
+                x: 1.5,
+                y: 2.0,
+                z: 0.0
+            }),
+            up
+        );
+        assert_eq!(
+            cone.local_normal_at(Point {
+                x: 0.0,
+                y: -1.0,
+                z: 0.5
+            }),
+            -up
+        );
+    }
+
+    #[test]
+    fn bounds_of_truncated_cone() {
+        let cone = Cone {
+            minimum: -5.0,
+            maximum: 3.0,
+            ..Default::default()
+        };
+        let bounds = cone.local_bounds();
+        assert_eq!(
+            bounds.min,
+            Point {
+                x: -5.0,
+                y: -5.0,
+                z: -5.0
+            }
+        );
+        assert_eq!(
+            bounds.max,
+            Point {
+                x: 5.0,
+                y: 3.0,
+                z: 5.0
+            }
+        );
+        assert!(!Cone::default().local_bounds().is_finite());
+    }
+
+    #[test]
+    fn normal_of_lamp_shade_points_outwards() {
+        // Lamp shade widening downwards, cut from the lower half between y=2 and y=2.5.
+        let cone = Cone {
+            transform: Transform::new(Matrix4x4::translation(0.0, 3.0, 0.0)).unwrap(),
+            minimum: -1.0,
+            maximum: -0.5,
+            ..Default::default()
+        };
+        let normal = cone.normal_at(Point {
+            x: 0.75,
+            y: 2.25,
+            z: 0.0,
+        });
+        let val = std::f64::consts::FRAC_1_SQRT_2 as Float;
+        assert!(approx_eq!(normal.x, val));
+        assert!(approx_eq!(normal.y, val));
+        assert!(approx_eq!(normal.z, 0.0));
+    }
+
+    #[test]
+    fn tangent_follows_cylindrical_mapping() {
+        let cone = Cone {
+            minimum: -1.0,
+            maximum: 1.0,
+            closed: true,
+            ..Default::default()
+        };
+        // On the side, the top cap and the bottom cap.
+        for point in [(0.6, 0.5, -0.8), (-0.3, 1.0, 0.2), (0.1, -1.0, 0.4)] {
+            let point = Point {
+                x: point.0,
+                y: point.1,
+                z: point.2,
+            };
+            let tangent = cone.local_tangent_at(point);
+            assert!(approx_eq!(tangent.dot(cone.local_normal_at(point)), 0.0));
+            let (u, v) = UvMapping::Cylindrical.map(point);
+            let (u2, v2) = UvMapping::Cylindrical.map(point + tangent * 0.01);
+            assert!(u2 > u, "{point:?}");
+            assert!(approx_eq!(v2, v), "{point:?}");
+        }
+    }
+}
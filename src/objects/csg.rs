@@ -0,0 +1,287 @@
+use crate::{BoundingBox, Intersection, Material, Point, Ray, Shape, Transform, Vector};
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum CsgOperation {
+    Union,
+    Intersection,
+    Difference,
+}
+
+impl CsgOperation {
+    /// Decides whether a hit on one child is part of the combined surface, given
+    /// whether the hit is inside the left and the right child.
+    pub fn intersection_allowed(&self, left_hit: bool, in_left: bool, in_right: bool) -> bool {
+        match self {
+            CsgOperation::Union => (left_hit && !in_right) || (!left_hit && !in_left),
+            CsgOperation::Intersection => (left_hit && in_right) || (!left_hit && in_left),
+            CsgOperation::Difference => (left_hit && !in_right) || (!left_hit && in_left),
+        }
+    }
+}
+
+/// Constructive solid geometry, combining two shapes using an operation.
+///
+/// Intersections refer to the child that was hit, so its material is used. Whether
+/// a ray is inside a child is tracked by counting its intersections, hence children
+/// should be closed shapes.
+#[derive(Debug)]
+pub struct Csg {
+    pub transform: Transform,
+    /// Never used for shading, intersections refer to the children.
+    pub material: Material,
+    pub operation: CsgOperation,
+    pub left: Box<dyn Shape>,
+    pub right: Box<dyn Shape>,
+}
+
+impl Csg {
+    pub fn new(operation: CsgOperation, left: Box<dyn Shape>, right: Box<dyn Shape>) -> Self {
+        Csg {
+            transform: Transform::identity(),
+            material: Material::default(),
+            operation,
+            left,
+            right,
+        }
+    }
+
+    // Keeps the intersections allowed by the operation, they have to be sorted by `t`
+    // and tagged with whether they belong to the left child.
+    fn filter_intersections<'a>(
+        &'a self,
+        intersections: Vec<(bool, Intersection<'a>)>,
+    ) -> Vec<Intersection<'a>> {
+        let (mut in_left, mut in_right) = (false, false);
+        let mut result = vec![];
+        for (left_hit, mut intersection) in intersections {
+            if self
+                .operation
+                .intersection_allowed(left_hit, in_left, in_right)
+            {
+                intersection.parents.push(self);
+                result.push(intersection);
+            }
+            if left_hit {
+                in_left = !in_left;
+            } else {
+                in_right = !in_right;
+            }
+        }
+        result
+    }
+}
+
+impl Shape for Csg {
+    fn transform(&self) -> &Transform {
+        &self.transform
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+
+    fn local_intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        let left = ray
+            .intersect(self.left.as_ref())
+            .into_iter()
+            .map(|i| (true, i));
+        let right = ray
+            .intersect(self.right.as_ref())
+            .into_iter()
+            .map(|i| (false, i));
+        let mut intersections: Vec<_> = left.chain(right).collect();
+        intersections.sort_by(|(_, a), (_, b)| a.t.total_cmp(&b.t));
+        self.filter_intersections(intersections)
+    }
+
+    fn local_bounds(&self) -> BoundingBox {
+        self.left.bounds().merge(&self.right.bounds())
+    }
+
+    // Intersections refer to the child that was hit, whose normal is used. Without
+    // knowing it, point away from the origin like a sphere.
+    fn local_normal_at(&self, point: Point) -> Vector {
+        point - Point::origin()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{approx_eq, Color, Cube, Cylinder, Float, Matrix4x4, Sphere};
+
+    fn test_csg(operation: CsgOperation) -> Csg {
+        Csg::new(
+            operation,
+            Box::new(Sphere::default()),
+            Box::new(Cube::default()),
+        )
+    }
+
+    #[test]
+    fn create_csg() {
+        let csg = test_csg(CsgOperation::Union);
+        assert_eq!(csg.operation, CsgOperation::Union);
+        assert_eq!(csg.transform, Transform::identity());
+    }
+
+    #[test]
+    fn evaluate_rule_for_csg_operation() {
+        use CsgOperation::*;
+        // Columns are left_hit, in_left, in_right and the result for each operation.
+        let cases = [
+            (true, true, true, [false, true, false]),
+            (true, true, false, [true, false, true]),
+            (true, false, true, [false, true, false]),
+            (true, false, false, [true, false, true]),
+            (false, true, true, [false, true, true]),
+            (false, true, false, [false, true, true]),
+            (false, false, true, [true, false, false]),
+            (false, false, false, [true, false, false]),
+        ];
+        for (left_hit, in_left, in_right, results) in cases {
+            for (operation, result) in [Union, Intersection, Difference].into_iter().zip(results) {
+                assert_eq!(
+                    operation.intersection_allowed(left_hit, in_left, in_right),
+                    result,
+                    "{:?} with left_hit={}, in_left={}, in_right={}",
+                    operation,
+                    left_hit,
+                    in_left,
+                    in_right
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn filter_list_of_intersections() {
+        let cases = [
+            (CsgOperation::Union, [0, 3]),
+            (CsgOperation::Intersection, [1, 2]),
+            (CsgOperation::Difference, [0, 1]),
+        ];
+        for (operation, [x0, x1]) in cases {
+            let csg = test_csg(operation);
+            let (left, right) = (csg.left.as_ref(), csg.right.as_ref());
+            let intersections = vec![
+                (true, Intersection::new(1.0, left)),
+                (false, Intersection::new(2.0, right)),
+                (true, Intersection::new(3.0, left)),
+                (false, Intersection::new(4.0, right)),
+            ];
+            let ts: Vec<Float> = intersections.iter().map(|(_, i)| i.t).collect();
+            let result = csg.filter_intersections(intersections);
+            assert_eq!(result.len(), 2);
+            assert_eq!(result[0].t, ts[x0]);
+            assert_eq!(result[1].t, ts[x1]);
+        }
+    }
+
+    #[test]
+    fn ray_misses_csg_object() {
+        let csg = test_csg(CsgOperation::Union);
+        let ray = Ray {
+            origin: Point {
+                x: 0.0,
+                y: 2.0,
+                z: -5.0,
+            },
+            direction: Vector {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0,
+            },
+        };
+        assert!(csg.local_intersect(&ray).is_empty());
+    }
+
+    #[test]
+    fn ray_hits_csg_object() {
+        let red = Material {
+            color: Color::red(),
+            ..Default::default()
+        };
+        let csg = Csg::new(
+            CsgOperation::Union,
+            Box::new(Sphere {
+                material: red.clone(),
+                ..Default::default()
+            }),
+            Box::new(Sphere {
+                transform: Transform::new(Matrix4x4::translation(0.0, 0.0, 0.5)).unwrap(),
+                ..Default::default()
+            }),
+        );
+        let ray = Ray {
+            origin: Point {
+                x: 0.0,
+                y: 0.0,
+                z: -5.0,
+            },
+            direction: Vector {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0,
+            },
+        };
+        let intersections = csg.local_intersect(&ray);
+        assert_eq!(intersections.len(), 2);
+        assert!(approx_eq!(intersections[0].t, 4.0));
+        assert_eq!(intersections[0].object, csg.left.as_ref());
+        assert_eq!(intersections[0].object.material(), &red);
+        assert!(approx_eq!(intersections[1].t, 6.5));
+        assert_eq!(intersections[1].object, csg.right.as_ref());
+        assert_eq!(intersections[1].parents, vec![&csg as &dyn Shape]);
+    }
+
+    #[test]
+    fn sphere_with_cylindrical_hole() {
+        let csg = Csg::new(
+            CsgOperation::Difference,
+            Box::new(Sphere::default()),
+            // Closed, so that rays along the axis still enter and leave it.
+            Box::new(Cylinder {
+                transform: Transform::new(Matrix4x4::scaling(0.5, 1.0, 0.5)).unwrap(),
+                minimum: -2.0,
+                maximum: 2.0,
+                closed: true,
+                ..Default::default()
+            }),
+        );
+        // Straight through the hole.
+        let ray = Ray {
+            origin: Point {
+                x: 0.0,
+                y: 5.0,
+                z: 0.0,
+            },
+            direction: Vector {
+                x: 0.0,
+                y: -1.0,
+                z: 0.0,
+            },
+        };
+        assert!(ray.intersect(&csg).is_empty());
+        // Through the wall of the hole, entering the sphere first.
+        let ray = Ray {
+            origin: Point {
+                x: 0.0,
+                y: 0.0,
+                z: -5.0,
+            },
+            direction: Vector {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0,
+            },
+        };
+        let intersections = ray.intersect(&csg);
+        let ts: Vec<Float> = intersections.iter().map(|i| i.t).collect();
+        assert_eq!(ts.len(), 4);
+        for (t, expected) in ts.into_iter().zip([4.0, 4.5, 5.5, 6.0]) {
+            assert!(approx_eq!(t, expected));
+        }
+        assert_eq!(intersections[1].object, csg.right.as_ref());
+    }
+}
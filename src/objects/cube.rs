@@ -0,0 +1,272 @@
+use crate::{BoundingBox, Float, Intersection, Material, Point, Ray, Shape, Transform, Vector};
+
+/// Axis-aligned cube, spanning from -1 to 1 along each axis in object space.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Cube {
+    pub transform: Transform,
+    pub material: Material,
+}
+
+// Returns where the ray enters and leaves the slab between -1 and 1 along one axis.
+fn check_axis(origin: Float, direction: Float) -> (Float, Float) {
+    // Division by zero yields infinities, which is what we want for parallel rays.
+    let tmin = (-1.0 - origin) / direction;
+    let tmax = (1.0 - origin) / direction;
+    if tmin > tmax {
+        (tmax, tmin)
+    } else {
+        (tmin, tmax)
+    }
+}
+
+impl Shape for Cube {
+    fn transform(&self) -> &Transform {
+        &self.transform
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+
+    fn local_intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        let (xtmin, xtmax) = check_axis(ray.origin.x, ray.direction.x);
+        let (ytmin, ytmax) = check_axis(ray.origin.y, ray.direction.y);
+        let (ztmin, ztmax) = check_axis(ray.origin.z, ray.direction.z);
+        // The ray is inside the cube where it is inside all three slabs.
+        let tmin = xtmin.max(ytmin).max(ztmin);
+        let tmax = xtmax.min(ytmax).min(ztmax);
+        if tmin > tmax {
+            return vec![];
+        }
+        vec![Intersection::new(tmin, self), Intersection::new(tmax, self)]
+    }
+
+    fn local_bounds(&self) -> BoundingBox {
+        BoundingBox {
+            min: Point {
+                x: -1.0,
+                y: -1.0,
+                z: -1.0,
+            },
+            max: Point {
+                x: 1.0,
+                y: 1.0,
+                z: 1.0,
+            },
+        }
+    }
+
+    fn local_normal_at(&self, point: Point) -> Vector {
+        // The face is given by the component with the largest magnitude.
+        let (x, y, z) = (point.x.abs(), point.y.abs(), point.z.abs());
+        if x >= y && x >= z {
+            Vector {
+                x: point.x,
+                y: 0.0,
+                z: 0.0,
+            }
+        } else if y >= z {
+            Vector {
+                x: 0.0,
+                y: point.y,
+                z: 0.0,
+            }
+        } else {
+            Vector {
+                x: 0.0,
+                y: 0.0,
+                z: point.z,
+            }
+        }
+    }
+
+    // Follows the faces of the cube mapping.
+    fn local_tangent_at(&self, point: Point) -> Vector {
+        let (x, y, z) = (point.x.abs(), point.y.abs(), point.z.abs());
+        if x >= y && x >= z {
+            Vector {
+                x: 0.0,
+                y: 0.0,
+                z: -point.x.signum(),
+            }
+        } else if y >= z {
+            Vector {
+                x: 1.0,
+                y: 0.0,
+                z: 0.0,
+            }
+        } else {
+            Vector {
+                x: point.z.signum(),
+                y: 0.0,
+                z: 0.0,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{approx_eq, Matrix4x4, UvMapping};
+    use std::f64::consts::FRAC_PI_4;
+
+    macro_rules! assert_tuple_approx_eq {
+        ($tuple1:expr, $tuple2:expr) => {
+            assert!(approx_eq!($tuple1.x, $tuple2.x));
+            assert!(approx_eq!($tuple1.y, $tuple2.y));
+            assert!(approx_eq!($tuple1.z, $tuple2.z));
+        };
+    }
+
+    fn ray(origin: (Float, Float, Float), direction: (Float, Float, Float)) -> Ray {
+        Ray {
+            origin: Point {
+                x: origin.0,
+                y: origin.1,
+                z: origin.2,
+            },
+            direction: Vector {
+                x: direction.0,
+                y: direction.1,
+                z: direction.2,
+            },
+        }
+    }
+
+    #[test]
+    fn cube_default_constructor() {
+        let cube = Cube::default();
+        assert_eq!(cube.transform, Transform::identity());
+        assert_eq!(cube.material, Material::default());
+    }
+
+    #[test]
+    fn ray_intersects_cube() {
+        let cube = Cube::default();
+        let cases = [
+            ((5.0, 0.5, 0.0), (-1.0, 0.0, 0.0), 4.0, 6.0), // +x
+            ((-5.0, 0.5, 0.0), (1.0, 0.0, 0.0), 4.0, 6.0), // -x
+            ((0.5, 5.0, 0.0), (0.0, -1.0, 0.0), 4.0, 6.0), // +y
+            ((0.5, -5.0, 0.0), (0.0, 1.0, 0.0), 4.0, 6.0), // -y
+            ((0.5, 0.0, 5.0), (0.0, 0.0, -1.0), 4.0, 6.0), // +z
+            ((0.5, 0.0, -5.0), (0.0, 0.0, 1.0), 4.0, 6.0), // -z
+            ((0.0, 0.5, 0.0), (0.0, 0.0, 1.0), -1.0, 1.0), // Inside.
+        ];
+        for (origin, direction, t1, t2) in cases {
+            let intersections = cube.local_intersect(&ray(origin, direction));
+            assert_eq!(intersections.len(), 2);
+            assert_eq!(intersections[0].t, t1);
+            assert_eq!(intersections[1].t, t2);
+        }
+    }
+
+    #[test]
+    fn ray_misses_cube() {
+        let cube = Cube::default();
+        let cases = [
+            ((-2.0, 0.0, 0.0), (0.2673, 0.5345, 0.8018)),
+            ((0.0, -2.0, 0.0), (0.8018, 0.2673, 0.5345)),
+            ((0.0, 0.0, -2.0), (0.5345, 0.8018, 0.2673)),
+            ((2.0, 0.0, 2.0), (0.0, 0.0, -1.0)),
+            ((0.0, 2.0, 2.0), (0.0, -1.0, 0.0)),
+            ((2.0, 2.0, 0.0), (-1.0, 0.0, 0.0)),
+        ];
+        for (origin, direction) in cases {
+            assert!(cube.local_intersect(&ray(origin, direction)).is_empty());
+        }
+    }
+
+    #[test]
+    fn normal_on_surface_of_cube() {
+        let cube = Cube::default();
+        let cases = [
+            ((1.0, 0.5, -0.8), (1.0, 0.0, 0.0)),
+            ((-1.0, -0.2, 0.9), (-1.0, 0.0, 0.0)),
+            ((-0.4, 1.0, -0.1), (0.0, 1.0, 0.0)),
+            ((0.3, -1.0, -0.7), (0.0, -1.0, 0.0)),
+            ((-0.6, 0.3, 1.0), (0.0, 0.0, 1.0)),
+            ((0.4, 0.4, -1.0), (0.0, 0.0, -1.0)),
+            ((1.0, 1.0, 1.0), (1.0, 0.0, 0.0)),
+            ((-1.0, -1.0, -1.0), (-1.0, 0.0, 0.0)),
+        ];
+        for ((x, y, z), (nx, ny, nz)) in cases {
+            assert_eq!(
+                cube.local_normal_at(Point { x, y, z }),
+                Vector {
+                    x: nx,
+                    y: ny,
+                    z: nz
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn ray_intersects_transformed_cube() {
+        // Box of size 2x1x1 centered at (0, 0, 5).
+        let cube = Cube {
+            transform: Transform::new(
+                Matrix4x4::translation(0.0, 0.0, 5.0).matmul(Matrix4x4::scaling(2.0, 0.5, 0.5)),
+            )
+            .unwrap(),
+            ..Default::default()
+        };
+        let intersections = ray((0.0, 0.0, 0.0), (0.0, 0.0, 1.0)).intersect(&cube);
+        assert_eq!(intersections.len(), 2);
+        assert!(approx_eq!(intersections[0].t, 4.5));
+        assert!(approx_eq!(intersections[1].t, 5.5));
+        // Scaling along y moves the top face down to 0.5.
+        let intersections = ray((0.0, 0.75, 0.0), (0.0, 0.0, 1.0)).intersect(&cube);
+        assert!(intersections.is_empty());
+    }
+
+    #[test]
+    fn normal_on_rotated_cube() {
+        let cube = Cube {
+            transform: Transform::new(Matrix4x4::rotation_y(FRAC_PI_4 as Float)).unwrap(),
+            ..Default::default()
+        };
+        // The edge of the +x and -z face now points towards -z.
+        let val = std::f64::consts::FRAC_1_SQRT_2 as Float;
+        let normal = cube.normal_at(Point {
+            x: 0.5,
+            y: 0.0,
+            z: -val - 0.5 * val,
+        });
+        assert_tuple_approx_eq!(
+            normal,
+            Vector {
+                x: val,
+                y: 0.0,
+                z: -val
+            }
+        );
+    }
+
+    #[test]
+    fn tangent_follows_cube_mapping() {
+        let cube = Cube::default();
+        for point in [
+            (1.0, 0.5, -0.8),
+            (-1.0, -0.2, 0.9),
+            (-0.4, 1.0, -0.1),
+            (0.3, -1.0, -0.7),
+            (-0.6, 0.3, 1.0),
+            (0.4, 0.4, -1.0),
+        ] {
+            let point = Point {
+                x: point.0,
+                y: point.1,
+                z: point.2,
+            };
+            let tangent = cube.local_tangent_at(point);
+            assert!(approx_eq!(tangent.dot(cube.local_normal_at(point)), 0.0));
+            // Moving along the tangent increases u and keeps v.
+            let (u, v) = UvMapping::Cube.map(point);
+            let (u2, v2) = UvMapping::Cube.map(point + tangent * 0.01);
+            assert!(approx_eq!(u2 - u, 0.01 / 8.0), "{point:?}");
+            assert!(approx_eq!(v2, v), "{point:?}");
+        }
+    }
+}
@@ -0,0 +1,320 @@
+use crate::{
+    epsilon, BoundingBox, Float, Intersection, Material, Point, Ray, Shape, Transform, Vector,
+};
+
+/// Cylinder with radius 1 around the y axis in object space.
+///
+/// It can be truncated to `minimum < y < maximum` (both exclusive), in which case
+/// `closed` determines whether the ends are capped.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Cylinder {
+    pub transform: Transform,
+    pub material: Material,
+    pub minimum: Float,
+    pub maximum: Float,
+    pub closed: bool,
+}
+
+impl Default for Cylinder {
+    fn default() -> Self {
+        Cylinder {
+            transform: Transform::identity(),
+            material: Material::default(),
+            minimum: -Float::INFINITY,
+            maximum: Float::INFINITY,
+            closed: false,
+        }
+    }
+}
+
+// Checks whether the ray at `t` is within `radius` of the y axis.
+pub(super) fn check_cap(ray: &Ray, t: Float, radius: Float) -> bool {
+    let x = ray.origin.x + t * ray.direction.x;
+    let z = ray.origin.z + t * ray.direction.z;
+    x * x + z * z <= radius * radius
+}
+
+impl Cylinder {
+    fn intersect_caps<'a>(&'a self, ray: &Ray, intersections: &mut Vec<Intersection<'a>>) {
+        // Caps only matter for closed cylinders, and rays parallel to them miss.
+        if !self.closed || ray.direction.y.abs() < epsilon() {
+            return;
+        }
+        for y in [self.minimum, self.maximum] {
+            let t = (y - ray.origin.y) / ray.direction.y;
+            if check_cap(ray, t, 1.0) {
+                intersections.push(Intersection::new(t, self));
+            }
+        }
+    }
+}
+
+impl Shape for Cylinder {
+    fn transform(&self) -> &Transform {
+        &self.transform
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+
+    fn local_intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        let mut intersections = vec![];
+        let a = ray.direction.x * ray.direction.x + ray.direction.z * ray.direction.z;
+        // Rays parallel to the y axis can only hit the caps.
+        if a.abs() >= epsilon() {
+            let b = 2.0 * (ray.origin.x * ray.direction.x + ray.origin.z * ray.direction.z);
+            let c = ray.origin.x * ray.origin.x + ray.origin.z * ray.origin.z - 1.0;
+            let discriminant = b * b - 4.0 * a * c;
+            if discriminant < 0.0 {
+                return intersections;
+            }
+            let sqrt = Float::sqrt(discriminant);
+            for t in [(-b - sqrt) / (2.0 * a), (-b + sqrt) / (2.0 * a)] {
+                let y = ray.origin.y + t * ray.direction.y;
+                if self.minimum < y && y < self.maximum {
+                    intersections.push(Intersection::new(t, self));
+                }
+            }
+        }
+        self.intersect_caps(ray, &mut intersections);
+        intersections
+    }
+
+    fn local_bounds(&self) -> BoundingBox {
+        BoundingBox {
+            min: Point {
+                x: -1.0,
+                y: self.minimum,
+                z: -1.0,
+            },
+            max: Point {
+                x: 1.0,
+                y: self.maximum,
+                z: 1.0,
+            },
+        }
+    }
+
+    fn local_normal_at(&self, point: Point) -> Vector {
+        let distance = point.x * point.x + point.z * point.z;
+        if distance < 1.0 && point.y >= self.maximum - epsilon() {
+            Vector {
+                x: 0.0,
+                y: 1.0,
+                z: 0.0,
+            }
+        } else if distance < 1.0 && point.y <= self.minimum + epsilon() {
+            Vector {
+                x: 0.0,
+                y: -1.0,
+                z: 0.0,
+            }
+        } else {
+            Vector {
+                x: point.x,
+                y: 0.0,
+                z: point.z,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{approx_eq, Matrix4x4, UvMapping};
+
+    fn ray(origin: (Float, Float, Float), direction: (Float, Float, Float)) -> Ray {
+        Ray {
+            origin: Point {
+                x: origin.0,
+                y: origin.1,
+                z: origin.2,
+            },
+            direction: Vector {
+                x: direction.0,
+                y: direction.1,
+                z: direction.2,
+            }
+            .normalize(),
+        }
+    }
+
+    #[test]
+    fn cylinder_default_constructor() {
+        let cylinder = Cylinder::default();
+        assert_eq!(cylinder.transform, Transform::identity());
+        assert_eq!(cylinder.material, Material::default());
+        assert_eq!(cylinder.minimum, -Float::INFINITY);
+        assert_eq!(cylinder.maximum, Float::INFINITY);
+        assert!(!cylinder.closed);
+    }
+
+    #[test]
+    fn ray_misses_cylinder() {
+        let cylinder = Cylinder::default();
+        let cases = [
+            ((1.0, 0.0, 0.0), (0.0, 1.0, 0.0)),
+            ((0.0, 0.0, 0.0), (0.0, 1.0, 0.0)),
+            ((0.0, 0.0, -5.0), (1.0, 1.0, 1.0)),
+        ];
+        for (origin, direction) in cases {
+            assert!(cylinder.local_intersect(&ray(origin, direction)).is_empty());
+        }
+    }
+
+    #[test]
+    fn ray_strikes_cylinder() {
+        let cylinder = Cylinder::default();
+        let cases = [
+            ((1.0, 0.0, -5.0), (0.0, 0.0, 1.0), 5.0, 5.0),
+            ((0.0, 0.0, -5.0), (0.0, 0.0, 1.0), 4.0, 6.0),
+            ((0.5, 0.0, -5.0), (0.1, 1.0, 1.0), 6.80798, 7.08872),
+        ];
+        for (origin, direction, t0, t1) in cases {
+            let intersections = cylinder.local_intersect(&ray(origin, direction));
+            assert_eq!(intersections.len(), 2);
+            assert!(approx_eq!(intersections[0].t, t0, atol = 1e-5));
+            assert!(approx_eq!(intersections[1].t, t1, atol = 1e-5));
+        }
+    }
+
+    #[test]
+    fn normal_on_cylinder() {
+        let cylinder = Cylinder::default();
+        let cases = [
+            ((1.0, 0.0, 0.0), (1.0, 0.0, 0.0)),
+            ((0.0, 5.0, -1.0), (0.0, 0.0, -1.0)),
+            ((0.0, -2.0, 1.0), (0.0, 0.0, 1.0)),
+            ((-1.0, 1.0, 0.0), (-1.0, 0.0, 0.0)),
+        ];
+        for ((x, y, z), (nx, ny, nz)) in cases {
+            assert_eq!(
+                cylinder.local_normal_at(Point { x, y, z }),
+                Vector {
+                    x: nx,
+                    y: ny,
+                    z: nz
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn intersect_constrained_cylinder() {
+        let cylinder = Cylinder {
+            minimum: 1.0,
+            maximum: 2.0,
+            ..Default::default()
+        };
+        let cases = [
+            ((0.0, 1.5, 0.0), (0.1, 1.0, 0.0), 0),
+            ((0.0, 3.0, -5.0), (0.0, 0.0, 1.0), 0),
+            ((0.0, 0.0, -5.0), (0.0, 0.0, 1.0), 0),
+            ((0.0, 2.0, -5.0), (0.0, 0.0, 1.0), 0),
+            ((0.0, 1.0, -5.0), (0.0, 0.0, 1.0), 0),
+            ((0.0, 1.5, -2.0), (0.0, 0.0, 1.0), 2),
+        ];
+        for (origin, direction, count) in cases {
+            assert_eq!(
+                cylinder.local_intersect(&ray(origin, direction)).len(),
+                count
+            );
+        }
+    }
+
+    #[test]
+    fn intersect_caps_of_closed_cylinder() {
+        let cylinder = Cylinder {
+            minimum: 1.0,
+            maximum: 2.0,
+            closed: true,
+            ..Default::default()
+        };
+        let cases = [
+            ((0.0, 3.0, 0.0), (0.0, -1.0, 0.0), 2),
+            ((0.0, 3.0, -2.0), (0.0, -1.0, 2.0), 2),
+            ((0.0, 4.0, -2.0), (0.0, -1.0, 1.0), 2), // Corner case.
+            ((0.0, 0.0, -2.0), (0.0, 1.0, 2.0), 2),
+            ((0.0, -1.0, -2.0), (0.0, 1.0, 1.0), 2), // Corner case.
+        ];
+        for (origin, direction, count) in cases {
+            assert_eq!(
+                cylinder.local_intersect(&ray(origin, direction)).len(),
+                count
+            );
+        }
+    }
+
+    #[test]
+    fn normal_on_end_caps() {
+        let cylinder = Cylinder {
+            minimum: 1.0,
+            maximum: 2.0,
+            closed: true,
+            ..Default::default()
+        };
+        let down = Vector {
+            x: 0.0,
+            y: -1.0,
+            z: 0.0,
+        };
+        let up = -down;
+        let cases = [
+            ((0.0, 1.0, 0.0), down),
+            ((0.5, 1.0, 0.0), down),
+            ((0.0, 1.0, 0.5), down),
+            ((0.0, 2.0, 0.0), up),
+            ((0.5, 2.0, 0.0), up),
+            ((0.0, 2.0, 0.5), up),
+        ];
+        for ((x, y, z), normal) in cases {
+            assert_eq!(cylinder.local_normal_at(Point { x, y, z }), normal);
+        }
+    }
+
+    #[test]
+    fn ray_intersects_transformed_cylinder() {
+        // Pipe of radius 0.5 along the x axis.
+        let cylinder = Cylinder {
+            transform: Transform::new(
+                Matrix4x4::rotation_z(std::f64::consts::FRAC_PI_2 as Float)
+                    .matmul(Matrix4x4::scaling(0.5, 1.0, 0.5)),
+            )
+            .unwrap(),
+            ..Default::default()
+        };
+        let intersections = ray((3.0, 0.0, -5.0), (0.0, 0.0, 1.0)).intersect(&cylinder);
+        assert_eq!(intersections.len(), 2);
+        assert!(approx_eq!(intersections[0].t, 4.5));
+        assert!(approx_eq!(intersections[1].t, 5.5));
+    }
+
+    #[test]
+    fn tangent_follows_cylindrical_mapping() {
+        let cylinder = Cylinder {
+            minimum: -1.0,
+            maximum: 1.0,
+            closed: true,
+            ..Default::default()
+        };
+        // On the side, the top cap and the bottom cap.
+        for point in [(0.6, 0.5, -0.8), (-0.3, 1.0, 0.2), (0.1, -1.0, 0.4)] {
+            let point = Point {
+                x: point.0,
+                y: point.1,
+                z: point.2,
+            };
+            let tangent = cylinder.local_tangent_at(point);
+            assert!(approx_eq!(
+                tangent.dot(cylinder.local_normal_at(point)),
+                0.0
+            ));
+            let (u, v) = UvMapping::Cylindrical.map(point);
+            let (u2, v2) = UvMapping::Cylindrical.map(point + tangent * 0.01);
+            assert!(u2 > u, "{point:?}");
+            assert!(approx_eq!(v2, v), "{point:?}");
+        }
+    }
+}
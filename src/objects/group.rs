@@ -0,0 +1,267 @@
+use crate::{BoundingBox, Intersection, Material, Point, Ray, Shape, Transform, Vector};
+
+/// Collection of shapes that are transformed together.
+///
+/// The transform of the group is applied on top of the transforms of its children,
+/// groups can be nested arbitrarily.
+#[derive(Debug, Default)]
+pub struct Group {
+    pub transform: Transform,
+    /// Never used for shading, intersections refer to the children.
+    pub material: Material,
+    pub children: Vec<Box<dyn Shape>>,
+}
+
+impl Shape for Group {
+    fn transform(&self) -> &Transform {
+        &self.transform
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+
+    fn local_intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        let mut intersections: Vec<Intersection> = self
+            .children
+            .iter()
+            .flat_map(|child| ray.intersect(child.as_ref()))
+            .collect();
+        for intersection in &mut intersections {
+            intersection.parents.push(self);
+        }
+        intersections.sort_by(|a, b| a.t.total_cmp(&b.t));
+        intersections
+    }
+
+    fn local_bounds(&self) -> BoundingBox {
+        self.children
+            .iter()
+            .fold(BoundingBox::empty(), |bounds, child| {
+                bounds.merge(&child.bounds())
+            })
+    }
+
+    // A group has no surface of its own, the normal of the child that was hit comes
+    // from `Intersection::normal_at`. Without a child, the best guess is the
+    // direction away from the origin of the group.
+    fn local_normal_at(&self, point: Point) -> Vector {
+        point - Point::origin()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{approx_eq, Float, Matrix4x4, Sphere};
+    use std::f64::consts::FRAC_PI_2;
+
+    macro_rules! assert_tuple_approx_eq {
+        ($tuple1:expr, $tuple2:expr) => {
+            assert!(approx_eq!($tuple1.x, $tuple2.x, atol = 1e-4));
+            assert!(approx_eq!($tuple1.y, $tuple2.y, atol = 1e-4));
+            assert!(approx_eq!($tuple1.z, $tuple2.z, atol = 1e-4));
+        };
+    }
+
+    fn ray_along_z(x: Float, y: Float, z: Float) -> Ray {
+        Ray {
+            origin: Point { x, y, z },
+            direction: Vector {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0,
+            },
+        }
+    }
+
+    #[test]
+    fn group_default_constructor() {
+        let group = Group::default();
+        assert_eq!(group.transform, Transform::identity());
+        assert!(group.children.is_empty());
+    }
+
+    #[test]
+    fn ray_misses_empty_group() {
+        let group = Group::default();
+        assert!(group
+            .local_intersect(&ray_along_z(0.0, 0.0, 0.0))
+            .is_empty());
+    }
+
+    #[test]
+    fn ray_intersects_nonempty_group() {
+        let group = Group {
+            children: vec![
+                Box::new(Sphere::default()),
+                Box::new(Sphere {
+                    transform: Transform::new(Matrix4x4::translation(0.0, 0.0, -3.0)).unwrap(),
+                    ..Default::default()
+                }),
+                Box::new(Sphere {
+                    transform: Transform::new(Matrix4x4::translation(5.0, 0.0, 0.0)).unwrap(),
+                    ..Default::default()
+                }),
+            ],
+            ..Default::default()
+        };
+        let intersections = group.local_intersect(&ray_along_z(0.0, 0.0, -5.0));
+        let objects: Vec<&dyn Shape> = intersections.iter().map(|i| i.object).collect();
+        let (s1, s2) = (group.children[0].as_ref(), group.children[1].as_ref());
+        assert_eq!(objects, vec![s2, s2, s1, s1]);
+        for intersection in &intersections {
+            assert_eq!(intersection.parents, vec![&group as &dyn Shape]);
+        }
+    }
+
+    #[test]
+    fn ray_intersects_transformed_group() {
+        let group = Group {
+            transform: Transform::new(Matrix4x4::scaling(2.0, 2.0, 2.0)).unwrap(),
+            children: vec![Box::new(Sphere {
+                transform: Transform::new(Matrix4x4::translation(5.0, 0.0, 0.0)).unwrap(),
+                ..Default::default()
+            })],
+            ..Default::default()
+        };
+        let intersections = ray_along_z(10.0, 0.0, -10.0).intersect(&group);
+        assert_eq!(intersections.len(), 2);
+    }
+
+    #[test]
+    fn convert_point_from_world_to_object_space() {
+        let g1 = Group {
+            transform: Transform::new(Matrix4x4::rotation_y(FRAC_PI_2 as Float)).unwrap(),
+            ..Default::default()
+        };
+        let g2 = Group {
+            transform: Transform::new(Matrix4x4::scaling(2.0, 2.0, 2.0)).unwrap(),
+            ..Default::default()
+        };
+        let sphere = Sphere {
+            transform: Transform::new(Matrix4x4::translation(5.0, 0.0, 0.0)).unwrap(),
+            ..Default::default()
+        };
+        let intersection = Intersection {
+            parents: vec![&g2, &g1],
+            ..Intersection::new(0.0, &sphere)
+        };
+        let point = intersection.world_to_object(Point {
+            x: -2.0,
+            y: 0.0,
+            z: -10.0,
+        });
+        assert_tuple_approx_eq!(
+            point,
+            Point {
+                x: 0.0,
+                y: 0.0,
+                z: -1.0
+            }
+        );
+    }
+
+    #[test]
+    fn convert_normal_from_object_to_world_space() {
+        let g1 = Group {
+            transform: Transform::new(Matrix4x4::rotation_y(FRAC_PI_2 as Float)).unwrap(),
+            ..Default::default()
+        };
+        let g2 = Group {
+            transform: Transform::new(Matrix4x4::scaling(1.0, 2.0, 3.0)).unwrap(),
+            ..Default::default()
+        };
+        let sphere = Sphere {
+            transform: Transform::new(Matrix4x4::translation(5.0, 0.0, 0.0)).unwrap(),
+            ..Default::default()
+        };
+        let intersection = Intersection {
+            parents: vec![&g2, &g1],
+            ..Intersection::new(0.0, &sphere)
+        };
+        let value = Float::sqrt(3.0) / 3.0;
+        let normal = intersection.normal_to_world(Vector {
+            x: value,
+            y: value,
+            z: value,
+        });
+        assert_tuple_approx_eq!(
+            normal,
+            Vector {
+                x: 0.2857,
+                y: 0.4286,
+                z: -0.8571
+            }
+        );
+    }
+
+    #[test]
+    fn normal_on_child_of_nested_groups() {
+        let g2 = Group {
+            transform: Transform::new(Matrix4x4::scaling(1.0, 2.0, 3.0)).unwrap(),
+            children: vec![Box::new(Sphere {
+                transform: Transform::new(Matrix4x4::translation(5.0, 0.0, 0.0)).unwrap(),
+                ..Default::default()
+            })],
+            ..Default::default()
+        };
+        let g1 = Group {
+            transform: Transform::new(Matrix4x4::rotation_y(FRAC_PI_2 as Float)).unwrap(),
+            children: vec![Box::new(g2)],
+            ..Default::default()
+        };
+        // Ray towards the center of the sphere, which is at (0, 0, -10) in world space.
+        let ray = Ray {
+            origin: Point {
+                x: 0.0,
+                y: 0.0,
+                z: 0.0,
+            },
+            direction: Vector {
+                x: 0.0,
+                y: 0.0,
+                z: -1.0,
+            },
+        };
+        let intersections = ray.intersect(&g1);
+        assert_eq!(intersections.len(), 2);
+        assert_eq!(intersections[0].parents.len(), 2);
+        assert_eq!(intersections[0].parents[1], &g1 as &dyn Shape);
+        let normal = intersections[0].normal_at(Point {
+            x: 1.7321,
+            y: 1.1547,
+            z: -5.5774,
+        });
+        assert_tuple_approx_eq!(
+            normal,
+            Vector {
+                x: 0.2857,
+                y: 0.4286,
+                z: -0.8571
+            }
+        );
+    }
+
+    #[test]
+    fn normal_on_group_itself() {
+        let group = Group {
+            transform: Transform::new(Matrix4x4::translation(0.0, 2.0, 0.0)).unwrap(),
+            children: vec![Box::new(Sphere::default())],
+            ..Default::default()
+        };
+        let normal = group.normal_at(Point {
+            x: 0.0,
+            y: 0.0,
+            z: 0.0,
+        });
+        assert_tuple_approx_eq!(
+            normal,
+            Vector {
+                x: 0.0,
+                y: -1.0,
+                z: 0.0
+            }
+        );
+    }
+}
@@ -0,0 +1,200 @@
+use super::{smooth_triangle::SmoothTriangle, triangle::Triangle};
+use crate::{BoundingBox, Bvh, Intersection, Material, Point, Ray, Shape, Transform, Vector};
+
+/// Single face of a `Mesh`.
+// Both variants are large anyway, boxing them would only add indirections.
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, PartialEq, Clone)]
+pub enum MeshTriangle {
+    Flat(Triangle),
+    Smooth(SmoothTriangle),
+}
+
+impl MeshTriangle {
+    fn shape(&self) -> &dyn Shape {
+        match self {
+            MeshTriangle::Flat(triangle) => triangle,
+            MeshTriangle::Smooth(triangle) => triangle,
+        }
+    }
+}
+
+/// Collection of triangles sharing a transform and a material.
+///
+/// Intersections refer to the triangle that was hit and have the mesh as parent,
+/// shading uses the material of the mesh instead of the ones of the triangles.
+#[derive(Debug, Clone)]
+pub struct Mesh {
+    pub transform: Transform,
+    pub material: Material,
+    triangles: Vec<MeshTriangle>,
+    bvh: Bvh,
+}
+
+impl Mesh {
+    pub fn new(triangles: Vec<MeshTriangle>, transform: Transform, material: Material) -> Self {
+        let bounds: Vec<BoundingBox> = triangles
+            .iter()
+            .map(|triangle| triangle.shape().bounds())
+            .collect();
+        Mesh {
+            transform,
+            material,
+            triangles,
+            bvh: Bvh::new(&bounds),
+        }
+    }
+
+    pub fn triangles(&self) -> &[MeshTriangle] {
+        &self.triangles
+    }
+}
+
+impl Shape for Mesh {
+    fn transform(&self) -> &Transform {
+        &self.transform
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+
+    fn shared_material(&self) -> Option<&Material> {
+        Some(&self.material)
+    }
+
+    fn local_intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        let mut intersections = vec![];
+        self.bvh.traverse(ray, |i| {
+            intersections.extend(ray.intersect(self.triangles[i].shape()))
+        });
+        for intersection in &mut intersections {
+            intersection.parents.push(self);
+        }
+        intersections
+    }
+
+    fn local_bounds(&self) -> BoundingBox {
+        self.triangles
+            .iter()
+            .fold(BoundingBox::empty(), |bounds, triangle| {
+                bounds.merge(&triangle.shape().bounds())
+            })
+    }
+
+    // The normals of the triangles are found through the intersections, which refer
+    // to the triangle that was hit. Without knowing it, point away from the origin.
+    fn local_normal_at(&self, point: Point) -> Vector {
+        point - Point::origin()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{approx_eq, Color, Float, Matrix4x4};
+
+    fn point(x: Float, y: Float, z: Float) -> Point {
+        Point { x, y, z }
+    }
+
+    fn test_mesh(transform: Transform, material: Material) -> Mesh {
+        let flat = Triangle::new(
+            point(0.0, 1.0, 0.0),
+            point(-1.0, 0.0, 0.0),
+            point(1.0, 0.0, 0.0),
+        );
+        // Same triangle moved back along z, with normals towards the camera.
+        let normal = Vector {
+            x: 0.0,
+            y: 0.0,
+            z: -1.0,
+        };
+        let smooth = SmoothTriangle::new(
+            point(0.0, 1.0, 2.0),
+            point(-1.0, 0.0, 2.0),
+            point(1.0, 0.0, 2.0),
+            normal,
+            normal,
+            normal,
+        );
+        Mesh::new(
+            vec![MeshTriangle::Flat(flat), MeshTriangle::Smooth(smooth)],
+            transform,
+            material,
+        )
+    }
+
+    #[test]
+    fn triangles_use_material_of_mesh() {
+        let transform = Transform::new(Matrix4x4::translation(1.0, 2.0, 3.0)).unwrap();
+        let material = Material {
+            color: Color::red(),
+            ..Default::default()
+        };
+        let mesh = test_mesh(transform, material.clone());
+        assert_eq!(mesh.transform(), &transform);
+        assert_eq!(mesh.material(), &material);
+        // The material is not copied into the triangles.
+        for triangle in mesh.triangles() {
+            assert_eq!(triangle.shape().transform(), &Transform::identity());
+            assert_eq!(triangle.shape().material(), &Material::default());
+        }
+        let ray = Ray {
+            origin: point(1.0, 2.5, -2.0),
+            direction: Vector {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0,
+            },
+        };
+        let intersections = ray.intersect(&mesh);
+        assert_eq!(intersections[0].parents, vec![&mesh as &dyn Shape]);
+        assert_eq!(intersections[0].material(), &material);
+        let comps = intersections[0].prepare_computations(&ray, &intersections);
+        assert_eq!(comps.material, &material);
+    }
+
+    #[test]
+    fn ray_intersects_all_triangles_of_transformed_mesh() {
+        let mesh = test_mesh(
+            Transform::new(Matrix4x4::translation(0.0, 0.0, 3.0)).unwrap(),
+            Material::default(),
+        );
+        let ray = Ray {
+            origin: point(0.0, 0.5, -2.0),
+            direction: Vector {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0,
+            },
+        };
+        let intersections = ray.intersect(&mesh);
+        assert_eq!(intersections.len(), 2);
+        assert!(approx_eq!(intersections[0].t, 5.0));
+        assert!(approx_eq!(intersections[1].t, 7.0));
+        assert_eq!(intersections[0].object, mesh.triangles()[0].shape());
+        assert_eq!(intersections[1].object, mesh.triangles()[1].shape());
+    }
+
+    #[test]
+    fn normal_on_mesh_uses_mesh_transform() {
+        let mesh = test_mesh(
+            Transform::new(Matrix4x4::rotation_y(std::f64::consts::PI as Float)).unwrap(),
+            Material::default(),
+        );
+        let ray = Ray {
+            origin: point(0.0, 0.5, 5.0),
+            direction: Vector {
+                x: 0.0,
+                y: 0.0,
+                z: -1.0,
+            },
+        };
+        let intersections = ray.intersect(&mesh);
+        let comps = intersections[0].prepare_computations(&ray, &intersections);
+        assert!(approx_eq!(comps.normal.x, 0.0));
+        assert!(approx_eq!(comps.normal.y, 0.0));
+        assert!(approx_eq!(comps.normal.z, 1.0));
+    }
+}
@@ -0,0 +1,194 @@
+use crate::{
+    epsilon, BoundingBox, Float, Intersection, Material, Point, Ray, Shape, Transform, Vector,
+};
+
+/// Infinite plane, which is the xz plane in object space.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Plane {
+    pub transform: Transform,
+    pub material: Material,
+}
+
+impl Shape for Plane {
+    fn transform(&self) -> &Transform {
+        &self.transform
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+
+    fn local_intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        // Rays parallel to the plane never hit it (coplanar rays are ignored too).
+        if ray.direction.y.abs() < epsilon() {
+            return vec![];
+        }
+        vec![Intersection::new(-ray.origin.y / ray.direction.y, self)]
+    }
+
+    fn local_bounds(&self) -> BoundingBox {
+        BoundingBox {
+            min: Point {
+                x: -Float::INFINITY,
+                y: 0.0,
+                z: -Float::INFINITY,
+            },
+            max: Point {
+                x: Float::INFINITY,
+                y: 0.0,
+                z: Float::INFINITY,
+            },
+        }
+    }
+
+    // Along x, matching the planar mapping.
+    fn local_tangent_at(&self, _point: Point) -> Vector {
+        Vector {
+            x: 1.0,
+            y: 0.0,
+            z: 0.0,
+        }
+    }
+
+    fn local_normal_at(&self, _point: Point) -> Vector {
+        Vector {
+            x: 0.0,
+            y: 1.0,
+            z: 0.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{approx_eq, get_hit, Color, Float, Matrix4x4, PointLight};
+
+    const UP: Vector = Vector {
+        x: 0.0,
+        y: 1.0,
+        z: 0.0,
+    };
+
+    #[test]
+    fn plane_default_constructor() {
+        let plane = Plane::default();
+        assert_eq!(plane.transform, Transform::identity());
+        assert_eq!(plane.material, Material::default());
+    }
+
+    #[test]
+    fn normal_of_plane_is_constant_everywhere() {
+        let plane = Plane::default();
+        for (x, z) in [(0.0, 0.0), (10.0, -10.0), (-5.0, 150.0)] {
+            assert_eq!(plane.local_normal_at(Point { x, y: 0.0, z }), UP);
+        }
+    }
+
+    #[test]
+    fn intersect_with_ray_parallel_to_plane() {
+        let plane = Plane::default();
+        let ray = Ray {
+            origin: Point {
+                x: 0.0,
+                y: 10.0,
+                z: 0.0,
+            },
+            direction: Vector {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0,
+            },
+        };
+        assert!(plane.local_intersect(&ray).is_empty());
+    }
+
+    #[test]
+    fn intersect_with_coplanar_ray() {
+        let plane = Plane::default();
+        let ray = Ray {
+            origin: Point::origin(),
+            direction: Vector {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0,
+            },
+        };
+        assert!(plane.local_intersect(&ray).is_empty());
+    }
+
+    #[test]
+    fn ray_intersecting_plane_from_above() {
+        let plane = Plane::default();
+        let ray = Ray {
+            origin: Point {
+                x: 0.0,
+                y: 1.0,
+                z: 0.0,
+            },
+            direction: Vector {
+                x: 0.0,
+                y: -1.0,
+                z: 0.0,
+            },
+        };
+        let intersections = plane.local_intersect(&ray);
+        assert_eq!(intersections.len(), 1);
+        assert_eq!(intersections[0].t, 1.0);
+        assert_eq!(intersections[0].object, &plane as &dyn Shape);
+    }
+
+    #[test]
+    fn ray_intersecting_plane_from_below() {
+        let plane = Plane::default();
+        let ray = Ray {
+            origin: Point {
+                x: 0.0,
+                y: -1.0,
+                z: 0.0,
+            },
+            direction: UP,
+        };
+        let intersections = plane.local_intersect(&ray);
+        assert_eq!(intersections.len(), 1);
+        assert_eq!(intersections[0].t, 1.0);
+    }
+
+    #[test]
+    fn shade_hit_on_transformed_plane() {
+        // Floor one unit below the origin, lit from straight above.
+        let plane = Plane {
+            transform: Transform::new(Matrix4x4::translation(0.0, -1.0, 0.0)).unwrap(),
+            ..Default::default()
+        };
+        let ray = Ray {
+            origin: Point {
+                x: 0.0,
+                y: 1.0,
+                z: -1.0,
+            },
+            direction: Vector {
+                x: 0.0,
+                y: -1.0,
+                z: 1.0,
+            }
+            .normalize(),
+        };
+        let intersections = ray.intersect(&plane);
+        let hit = get_hit(&intersections).unwrap();
+        assert!(approx_eq!(hit.t, Float::sqrt(8.0)));
+        let comps = hit.prepare_computations(&ray, &intersections);
+        assert_eq!(comps.normal, UP);
+        let light = PointLight {
+            position: Point {
+                x: 0.0,
+                y: 10.0,
+                z: 1.0,
+            },
+            intensity: Color::white(),
+        };
+        let color = plane.material().lighting(light, &comps, false);
+        // Ambient plus full diffuse, the eye is far off the reflection vector.
+        assert!(approx_eq!(color.r, 1.0, atol = 1e-3));
+    }
+}
@@ -0,0 +1,180 @@
+use super::triangle::Triangle;
+use crate::{BoundingBox, Float, Intersection, Material, Point, Ray, Shape, Transform, Vector};
+
+/// Triangle with a normal per corner, which are interpolated across the face.
+#[derive(Debug, PartialEq, Clone)]
+pub struct SmoothTriangle {
+    pub transform: Transform,
+    pub material: Material,
+    // Only used for the corners and the intersection, its transform is ignored.
+    triangle: Triangle,
+    n1: Vector,
+    n2: Vector,
+    n3: Vector,
+}
+
+impl SmoothTriangle {
+    pub fn new(p1: Point, p2: Point, p3: Point, n1: Vector, n2: Vector, n3: Vector) -> Self {
+        SmoothTriangle {
+            transform: Transform::identity(),
+            material: Material::default(),
+            triangle: Triangle::new(p1, p2, p3),
+            n1,
+            n2,
+            n3,
+        }
+    }
+
+    /// Sets the texture coordinates of the corners, which are interpolated across the face.
+    pub fn with_texture_coordinates(
+        mut self,
+        t1: (Float, Float),
+        t2: (Float, Float),
+        t3: (Float, Float),
+    ) -> Self {
+        self.triangle = self.triangle.with_texture_coordinates(t1, t2, t3);
+        self
+    }
+
+    pub fn p1(&self) -> Point {
+        self.triangle.p1()
+    }
+
+    pub fn p2(&self) -> Point {
+        self.triangle.p2()
+    }
+
+    pub fn p3(&self) -> Point {
+        self.triangle.p3()
+    }
+
+    pub fn n1(&self) -> Vector {
+        self.n1
+    }
+
+    pub fn n2(&self) -> Vector {
+        self.n2
+    }
+
+    pub fn n3(&self) -> Vector {
+        self.n3
+    }
+
+    pub fn texture_coordinates(&self) -> Option<[(Float, Float); 3]> {
+        self.triangle.texture_coordinates()
+    }
+}
+
+impl Shape for SmoothTriangle {
+    fn transform(&self) -> &Transform {
+        &self.transform
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+
+    fn local_intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        match self.triangle.intersect_uv(ray) {
+            Some((t, u, v)) => vec![Intersection::with_uv(t, self, u, v)],
+            None => vec![],
+        }
+    }
+
+    fn local_bounds(&self) -> BoundingBox {
+        self.triangle.local_bounds()
+    }
+
+    fn local_normal_at(&self, point: Point) -> Vector {
+        self.triangle.local_normal_at(point)
+    }
+
+    fn local_tangent_at(&self, point: Point) -> Vector {
+        self.triangle.local_tangent_at(point)
+    }
+
+    fn local_normal_at_uv(&self, _point: Point, u: Float, v: Float) -> Vector {
+        self.n2 * u + self.n3 * v + self.n1 * (1.0 - u - v)
+    }
+
+    fn texture_coordinates_at(&self, u: Float, v: Float) -> Option<(Float, Float)> {
+        self.triangle.texture_coordinates_at(u, v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::approx_eq;
+
+    macro_rules! assert_tuple_approx_eq {
+        ($tuple1:expr, $tuple2:expr) => {
+            assert!(approx_eq!($tuple1.x, $tuple2.x, atol = 1e-5));
+            assert!(approx_eq!($tuple1.y, $tuple2.y, atol = 1e-5));
+            assert!(approx_eq!($tuple1.z, $tuple2.z, atol = 1e-5));
+        };
+    }
+
+    fn point(x: Float, y: Float, z: Float) -> Point {
+        Point { x, y, z }
+    }
+
+    fn vector(x: Float, y: Float, z: Float) -> Vector {
+        Vector { x, y, z }
+    }
+
+    fn test_triangle() -> SmoothTriangle {
+        SmoothTriangle::new(
+            point(0.0, 1.0, 0.0),
+            point(-1.0, 0.0, 0.0),
+            point(1.0, 0.0, 0.0),
+            vector(0.0, 1.0, 0.0),
+            vector(-1.0, 0.0, 0.0),
+            vector(1.0, 0.0, 0.0),
+        )
+    }
+
+    #[test]
+    fn construct_smooth_triangle() {
+        let triangle = test_triangle();
+        assert_eq!(triangle.p1(), point(0.0, 1.0, 0.0));
+        assert_eq!(triangle.p2(), point(-1.0, 0.0, 0.0));
+        assert_eq!(triangle.p3(), point(1.0, 0.0, 0.0));
+        assert_eq!(triangle.n1(), vector(0.0, 1.0, 0.0));
+        assert_eq!(triangle.n2(), vector(-1.0, 0.0, 0.0));
+        assert_eq!(triangle.n3(), vector(1.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn intersection_stores_u_and_v() {
+        let ray = Ray {
+            origin: point(-0.2, 0.3, -2.0),
+            direction: vector(0.0, 0.0, 1.0),
+        };
+        let triangle = test_triangle();
+        let intersections = triangle.local_intersect(&ray);
+        assert_eq!(intersections.len(), 1);
+        assert!(approx_eq!(intersections[0].u, 0.45));
+        assert!(approx_eq!(intersections[0].v, 0.25));
+    }
+
+    #[test]
+    fn normal_is_interpolated_using_u_and_v() {
+        let triangle = test_triangle();
+        let intersection = Intersection::with_uv(1.0, &triangle, 0.45, 0.25);
+        let normal = triangle.normal_at_uv(Point::origin(), intersection.u, intersection.v);
+        assert_tuple_approx_eq!(normal, vector(-0.5547, 0.83205, 0.0));
+    }
+
+    #[test]
+    fn prepare_normal_on_smooth_triangle() {
+        let triangle = test_triangle();
+        let intersections = [Intersection::with_uv(1.0, &triangle, 0.45, 0.25)];
+        let ray = Ray {
+            origin: point(-0.2, 0.3, -2.0),
+            direction: vector(0.0, 0.0, 1.0),
+        };
+        let comps = intersections[0].prepare_computations(&ray, &intersections);
+        assert_tuple_approx_eq!(comps.normal, vector(-0.5547, 0.83205, 0.0));
+    }
+}
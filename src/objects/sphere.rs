@@ -1,42 +1,62 @@
-use crate::{Invertible, Material, Matrix4x4, Point, Vector};
+use crate::{BoundingBox, Float, Intersection, Material, Point, Ray, Shape, Transform, Vector};
 
-#[derive(Debug, PartialEq, Clone, Copy)]
+#[derive(Debug, PartialEq, Clone, Default)]
 pub struct Sphere {
-    pub transform: Matrix4x4,
+    pub transform: Transform,
     pub material: Material,
 }
 
-impl Default for Sphere {
-    fn default() -> Self {
-        Sphere {
-            transform: Matrix4x4::identity(),
-            material: Material::default(),
+impl Shape for Sphere {
+    fn transform(&self) -> &Transform {
+        &self.transform
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+
+    fn local_intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        let sphere_to_ray = ray.origin - Point::origin();
+        let a = ray.direction.squared_length();
+        let b = 2.0 * ray.direction.dot(sphere_to_ray);
+        let c = sphere_to_ray.squared_length() - 1.0;
+        let discriminant = b * b - 4.0 * a * c;
+        if discriminant < 0.0 {
+            vec![]
+        } else {
+            let sqrt = Float::sqrt(discriminant);
+            let div = 1.0 / (2.0 * a);
+            vec![
+                Intersection::new((-b - sqrt) * div, self),
+                Intersection::new((-b + sqrt) * div, self),
+            ]
+        }
+    }
+
+    fn local_bounds(&self) -> BoundingBox {
+        BoundingBox {
+            min: Point {
+                x: -1.0,
+                y: -1.0,
+                z: -1.0,
+            },
+            max: Point {
+                x: 1.0,
+                y: 1.0,
+                z: 1.0,
+            },
         }
     }
-}
 
-impl Sphere {
-    pub fn normal_at(&self, world_point: Point) -> Vector {
-        let inv_transform = self
-            .transform
-            .inverse()
-            .expect("transform should be invertible");
-
-        let object_point = inv_transform.matmul(world_point);
-        let mut object_normal = object_point;
-        object_normal.data[3][0] = 0.0;
-        let mut world_normal = inv_transform.transpose().matmul(object_normal);
-        world_normal.data[3][0] = 0.0;
-        Vector::try_from(world_normal)
-            .expect("should be convertible to Vector")
-            .normalize()
+    fn local_normal_at(&self, point: Point) -> Vector {
+        point - Point::origin()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{approx_eq, Float};
+    use crate::{approx_eq, Matrix4x4};
     use std::iter::zip;
 
     macro_rules! assert_matrix_approx_eq {
@@ -60,19 +80,19 @@ mod tests {
     #[test]
     fn sphere_default_constructor() {
         let sphere = Sphere::default();
-        assert_eq!(sphere.transform, Matrix4x4::identity());
+        assert_eq!(sphere.transform, Transform::identity());
         assert_eq!(sphere.material, Material::default());
     }
 
     #[test]
     fn sphere_with_non_default_transform() {
-        let transform = Matrix4x4::translation(2.0, 3.0, 4.0);
+        let transform = Transform::new(Matrix4x4::translation(2.0, 3.0, 4.0)).unwrap();
         let material = Material::default();
         let sphere = Sphere {
             transform,
             material,
         };
-        assert_matrix_approx_eq!(sphere.transform, transform);
+        assert_matrix_approx_eq!(sphere.transform.matrix(), transform.matrix());
     }
 
     #[test]
@@ -120,7 +140,7 @@ mod tests {
     #[test]
     fn normal_on_a_translated_sphere() {
         let sphere = Sphere {
-            transform: Matrix4x4::translation(0.0, 1.0, 0.0),
+            transform: Transform::new(Matrix4x4::translation(0.0, 1.0, 0.0)).unwrap(),
             material: Material::default(),
         };
         let normal = sphere.normal_at(Point {
@@ -141,8 +161,11 @@ mod tests {
     #[test]
     fn normal_on_a_transformed_sphere() {
         let sphere = Sphere {
-            transform: Matrix4x4::scaling(1.0, 0.5, 1.0)
-                .matmul(Matrix4x4::rotation_z(std::f64::consts::PI as Float / 5.0)),
+            transform: Transform::new(
+                Matrix4x4::scaling(1.0, 0.5, 1.0)
+                    .matmul(Matrix4x4::rotation_z(std::f64::consts::PI as Float / 5.0)),
+            )
+            .unwrap(),
             material: Material::default(),
         };
         let normal = sphere.normal_at(Point {
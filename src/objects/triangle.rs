@@ -0,0 +1,296 @@
+use crate::{
+    epsilon, BoundingBox, Float, Intersection, Material, Point, Ray, Shape, Transform, Vector,
+};
+
+/// Flat triangle given by its three corners, optionally with texture coordinates.
+///
+/// The edges and the face normal are precomputed, hence the corners can only be
+/// set on construction.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Triangle {
+    pub transform: Transform,
+    pub material: Material,
+    p1: Point,
+    p2: Point,
+    p3: Point,
+    e1: Vector,
+    e2: Vector,
+    normal: Vector,
+    texture_coordinates: Option<[(Float, Float); 3]>,
+    // Direction of increasing u, along the first edge without texture coordinates.
+    tangent: Vector,
+}
+
+impl Triangle {
+    pub fn new(p1: Point, p2: Point, p3: Point) -> Self {
+        let e1 = p2 - p1;
+        let e2 = p3 - p1;
+        Triangle {
+            transform: Transform::identity(),
+            material: Material::default(),
+            p1,
+            p2,
+            p3,
+            e1,
+            e2,
+            normal: e2.cross(e1).normalize(),
+            texture_coordinates: None,
+            tangent: e1,
+        }
+    }
+
+    /// Sets the texture coordinates of the corners, which are interpolated across the face.
+    pub fn with_texture_coordinates(
+        mut self,
+        t1: (Float, Float),
+        t2: (Float, Float),
+        t3: (Float, Float),
+    ) -> Self {
+        self.texture_coordinates = Some([t1, t2, t3]);
+        // Solves e1 = du1 * tangent + dv1 * bitangent and e2 likewise for the tangent.
+        let (du1, dv1) = (t2.0 - t1.0, t2.1 - t1.1);
+        let (du2, dv2) = (t3.0 - t1.0, t3.1 - t1.1);
+        let det = du1 * dv2 - du2 * dv1;
+        if det.abs() >= epsilon() {
+            self.tangent = (self.e1 * dv2 - self.e2 * dv1) / det;
+        }
+        self
+    }
+
+    pub fn p1(&self) -> Point {
+        self.p1
+    }
+
+    pub fn p2(&self) -> Point {
+        self.p2
+    }
+
+    pub fn p3(&self) -> Point {
+        self.p3
+    }
+
+    pub fn e1(&self) -> Vector {
+        self.e1
+    }
+
+    pub fn e2(&self) -> Vector {
+        self.e2
+    }
+
+    pub fn normal(&self) -> Vector {
+        self.normal
+    }
+
+    pub fn texture_coordinates(&self) -> Option<[(Float, Float); 3]> {
+        self.texture_coordinates
+    }
+
+    // Möller–Trumbore algorithm, returns t and the barycentric u and v of the hit.
+    pub(super) fn intersect_uv(&self, ray: &Ray) -> Option<(Float, Float, Float)> {
+        let dir_cross_e2 = ray.direction.cross(self.e2);
+        let det = self.e1.dot(dir_cross_e2);
+        // The determinant scales with the edge lengths, so the parallel test has to as well.
+        let scale = self.e1.length() * self.e2.length() * ray.direction.length();
+        if det.abs() < epsilon() * scale {
+            return None;
+        }
+        let f = 1.0 / det;
+        let p1_to_origin = ray.origin - self.p1;
+        let u = f * p1_to_origin.dot(dir_cross_e2);
+        if !(0.0..=1.0).contains(&u) {
+            return None;
+        }
+        let origin_cross_e1 = p1_to_origin.cross(self.e1);
+        let v = f * ray.direction.dot(origin_cross_e1);
+        if v < 0.0 || u + v > 1.0 {
+            return None;
+        }
+        Some((f * self.e2.dot(origin_cross_e1), u, v))
+    }
+}
+
+impl Shape for Triangle {
+    fn transform(&self) -> &Transform {
+        &self.transform
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+
+    fn local_intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        match self.intersect_uv(ray) {
+            Some((t, u, v)) => vec![Intersection::with_uv(t, self, u, v)],
+            None => vec![],
+        }
+    }
+
+    fn local_bounds(&self) -> BoundingBox {
+        BoundingBox::from_points([self.p1, self.p2, self.p3])
+    }
+
+    fn local_normal_at(&self, _point: Point) -> Vector {
+        self.normal
+    }
+
+    fn texture_coordinates_at(&self, u: Float, v: Float) -> Option<(Float, Float)> {
+        let [t1, t2, t3] = self.texture_coordinates?;
+        let w = 1.0 - u - v;
+        Some((
+            t2.0 * u + t3.0 * v + t1.0 * w,
+            t2.1 * u + t3.1 * v + t1.1 * w,
+        ))
+    }
+
+    fn local_tangent_at(&self, _point: Point) -> Vector {
+        self.tangent
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{approx_eq, Matrix4x4};
+
+    fn point(x: Float, y: Float, z: Float) -> Point {
+        Point { x, y, z }
+    }
+
+    fn vector(x: Float, y: Float, z: Float) -> Vector {
+        Vector { x, y, z }
+    }
+
+    fn test_triangle() -> Triangle {
+        Triangle::new(
+            point(0.0, 1.0, 0.0),
+            point(-1.0, 0.0, 0.0),
+            point(1.0, 0.0, 0.0),
+        )
+    }
+
+    #[test]
+    fn construct_triangle() {
+        let triangle = test_triangle();
+        assert_eq!(triangle.p1(), point(0.0, 1.0, 0.0));
+        assert_eq!(triangle.p2(), point(-1.0, 0.0, 0.0));
+        assert_eq!(triangle.p3(), point(1.0, 0.0, 0.0));
+        assert_eq!(triangle.e1(), vector(-1.0, -1.0, 0.0));
+        assert_eq!(triangle.e2(), vector(1.0, -1.0, 0.0));
+        assert_eq!(triangle.normal(), vector(0.0, 0.0, -1.0));
+        assert_eq!(triangle.transform, Transform::identity());
+        assert_eq!(triangle.material, Material::default());
+    }
+
+    #[test]
+    fn normal_on_triangle_is_constant() {
+        let triangle = test_triangle();
+        for p in [
+            point(0.0, 0.5, 0.0),
+            point(-0.5, 0.75, 0.0),
+            point(0.5, 0.25, 0.0),
+        ] {
+            assert_eq!(triangle.local_normal_at(p), triangle.normal());
+        }
+    }
+
+    #[test]
+    fn texture_coordinates_are_interpolated() {
+        let triangle = test_triangle();
+        assert_eq!(triangle.texture_coordinates_at(0.25, 0.5), None);
+        let triangle = triangle.with_texture_coordinates((0.5, 1.0), (0.0, 0.0), (1.0, 0.0));
+        let (u, v) = triangle.texture_coordinates_at(0.25, 0.5).unwrap();
+        assert!(approx_eq!(u, 0.625));
+        assert!(approx_eq!(v, 0.25));
+    }
+
+    #[test]
+    fn tangent_follows_texture_coordinates() {
+        let triangle = test_triangle();
+        // Along the first edge without texture coordinates.
+        assert_eq!(triangle.local_tangent_at(Point::origin()), triangle.e1());
+        let triangle = test_triangle().with_texture_coordinates((0.5, 1.0), (0.0, 0.0), (1.0, 0.0));
+        assert_eq!(
+            triangle.local_tangent_at(Point::origin()).normalize(),
+            vector(1.0, 0.0, 0.0)
+        );
+        // Texture rotated by a quarter turn, u increases downwards.
+        let triangle = test_triangle().with_texture_coordinates((0.0, 0.5), (1.0, 0.0), (1.0, 1.0));
+        assert_eq!(
+            triangle.local_tangent_at(Point::origin()).normalize(),
+            vector(0.0, -1.0, 0.0)
+        );
+        // Degenerate texture coordinates keep the first edge.
+        let triangle = test_triangle().with_texture_coordinates((0.5, 0.5), (0.5, 0.5), (0.5, 0.5));
+        assert_eq!(triangle.local_tangent_at(Point::origin()), triangle.e1());
+    }
+
+    #[test]
+    fn ray_parallel_to_triangle_misses() {
+        let ray = Ray {
+            origin: point(0.0, -1.0, -2.0),
+            direction: vector(0.0, 1.0, 0.0),
+        };
+        assert!(test_triangle().local_intersect(&ray).is_empty());
+    }
+
+    #[test]
+    fn ray_misses_triangle_edges() {
+        let triangle = test_triangle();
+        // Beyond the p1-p3, p1-p2 and p2-p3 edge, respectively.
+        for origin in [
+            point(1.0, 1.0, -2.0),
+            point(-1.0, 1.0, -2.0),
+            point(0.0, -1.0, -2.0),
+        ] {
+            let ray = Ray {
+                origin,
+                direction: vector(0.0, 0.0, 1.0),
+            };
+            assert!(triangle.local_intersect(&ray).is_empty());
+        }
+    }
+
+    #[test]
+    fn ray_strikes_triangle() {
+        let triangle = test_triangle();
+        let ray = Ray {
+            origin: point(0.0, 0.5, -2.0),
+            direction: vector(0.0, 0.0, 1.0),
+        };
+        let intersections = triangle.local_intersect(&ray);
+        assert_eq!(intersections.len(), 1);
+        assert!(approx_eq!(intersections[0].t, 2.0));
+    }
+
+    #[test]
+    fn ray_strikes_small_triangle() {
+        // Edges of 1 mm in a model authored in metres.
+        let triangle = Triangle::new(
+            point(0.0, 0.001, 0.0),
+            point(-0.001, 0.0, 0.0),
+            point(0.001, 0.0, 0.0),
+        );
+        let ray = Ray {
+            origin: point(0.0, 0.0005, -2.0),
+            direction: vector(0.0, 0.0, 1.0),
+        };
+        let intersections = triangle.local_intersect(&ray);
+        assert_eq!(intersections.len(), 1);
+        assert!(approx_eq!(intersections[0].t, 2.0));
+    }
+
+    #[test]
+    fn ray_intersects_transformed_triangle() {
+        let triangle = Triangle {
+            transform: Transform::new(Matrix4x4::translation(0.0, 0.0, 3.0)).unwrap(),
+            ..test_triangle()
+        };
+        let ray = Ray {
+            origin: point(0.0, 0.5, -2.0),
+            direction: vector(0.0, 0.0, 1.0),
+        };
+        let intersections = ray.intersect(&triangle);
+        assert_eq!(intersections.len(), 1);
+        assert!(approx_eq!(intersections[0].t, 5.0));
+    }
+}
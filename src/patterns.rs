@@ -0,0 +1,109 @@
+pub mod blend;
+pub mod checker;
+pub mod clouds;
+pub mod gradient;
+pub mod marble;
+pub mod perturbed;
+pub mod ring;
+pub mod solid;
+pub mod stripe;
+pub mod texture_map;
+pub mod wood;
+
+use crate::{Color, Float, Point, Transform};
+use std::fmt::Debug;
+
+/// Common interface of everything that can color the surface of a `Material`.
+///
+/// Like shapes, patterns only have to deal with their own pattern space, which is
+/// related to the object space of the shape (or the space of the enclosing pattern
+/// when nested) by `transform`.
+pub trait Pattern: Debug + Send + Sync {
+    fn transform(&self) -> &Transform;
+
+    fn local_pattern_at(&self, point: Point) -> Color;
+
+    /// Color at a point in object space (or in the space of the enclosing pattern).
+    fn pattern_at(&self, point: Point) -> Color {
+        let pattern_point = self
+            .transform()
+            .inverse()
+            .matmul(point)
+            .try_into()
+            .expect("should be convertible to Point");
+        self.local_pattern_at(pattern_point)
+    }
+
+    /// Like `pattern_at`, with the texture coordinates stored on the surface if there
+    /// are any. Only patterns using them, like `TextureMapPattern`, override this.
+    fn pattern_at_uv(&self, point: Point, _texture_coordinates: Option<(Float, Float)>) -> Color {
+        self.pattern_at(point)
+    }
+}
+
+// Patterns are compared by identity, like shapes.
+impl PartialEq for dyn Pattern + '_ {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::addr_eq(self, other)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::Matrix4x4;
+
+    /// Pattern returning the coordinates of the point as color.
+    #[derive(Debug, Default)]
+    pub(crate) struct TestPattern {
+        pub transform: Transform,
+    }
+
+    impl Pattern for TestPattern {
+        fn transform(&self) -> &Transform {
+            &self.transform
+        }
+
+        fn local_pattern_at(&self, point: Point) -> Color {
+            Color {
+                r: point.x,
+                g: point.y,
+                b: point.z,
+            }
+        }
+    }
+
+    #[test]
+    fn default_pattern_transform() {
+        let pattern = TestPattern::default();
+        assert_eq!(pattern.transform, Transform::identity());
+    }
+
+    #[test]
+    fn pattern_with_transform() {
+        let pattern = TestPattern {
+            transform: Transform::new(Matrix4x4::scaling(2.0, 2.0, 2.0)).unwrap(),
+        };
+        let point = Point {
+            x: 2.0,
+            y: 3.0,
+            z: 4.0,
+        };
+        assert_eq!(
+            pattern.pattern_at(point),
+            Color {
+                r: 1.0,
+                g: 1.5,
+                b: 2.0,
+            }
+        );
+    }
+
+    #[test]
+    fn patterns_are_compared_by_identity() {
+        let a = TestPattern::default();
+        let b = TestPattern::default();
+        assert!(&a as &dyn Pattern == &a as &dyn Pattern);
+        assert!(&a as &dyn Pattern != &b as &dyn Pattern);
+    }
+}
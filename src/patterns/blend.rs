@@ -0,0 +1,76 @@
+use crate::{Color, Float, Pattern, Point, Transform};
+use std::sync::Arc;
+
+/// Mixes two patterns, `weight` is the fraction of `b` in the result.
+#[derive(Debug, Clone)]
+pub struct BlendPattern {
+    pub transform: Transform,
+    pub a: Arc<dyn Pattern>,
+    pub b: Arc<dyn Pattern>,
+    pub weight: Float,
+}
+
+impl BlendPattern {
+    /// Blends both patterns equally.
+    pub fn new(a: Arc<dyn Pattern>, b: Arc<dyn Pattern>) -> Self {
+        BlendPattern {
+            transform: Transform::identity(),
+            a,
+            b,
+            weight: 0.5,
+        }
+    }
+}
+
+impl Pattern for BlendPattern {
+    fn transform(&self) -> &Transform {
+        &self.transform
+    }
+
+    fn local_pattern_at(&self, point: Point) -> Color {
+        self.a.pattern_at(point) * (1.0 - self.weight) + self.b.pattern_at(point) * self.weight
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Matrix4x4, SolidPattern, StripePattern};
+
+    fn point(x: Float, y: Float, z: Float) -> Point {
+        Point { x, y, z }
+    }
+
+    #[test]
+    fn blend_averages_patterns() {
+        let stripes = StripePattern::new(Color::white(), Color::black());
+        let crossed = StripePattern {
+            transform: Transform::new(Matrix4x4::rotation_y(std::f64::consts::FRAC_PI_2 as Float))
+                .unwrap(),
+            ..stripes.clone()
+        };
+        let pattern = BlendPattern::new(Arc::new(stripes), Arc::new(crossed));
+        assert_eq!(pattern.pattern_at(point(0.5, 0.0, -0.5)), Color::white());
+        assert_eq!(pattern.pattern_at(point(0.5, 0.0, 0.5)), Color::gray());
+        assert_eq!(pattern.pattern_at(point(1.5, 0.0, 0.5)), Color::black());
+    }
+
+    #[test]
+    fn blend_with_weight() {
+        let pattern = BlendPattern {
+            weight: 0.25,
+            ..BlendPattern::new(
+                Arc::new(SolidPattern::new(Color::white())),
+                Arc::new(SolidPattern::new(Color::black())),
+            )
+        };
+        assert_eq!(
+            pattern.pattern_at(Point::origin()),
+            Color {
+                r: 0.75,
+                g: 0.75,
+                b: 0.75,
+            }
+        );
+    }
+}
@@ -0,0 +1,76 @@
+use crate::{Color, Pattern, Point, SolidPattern, Transform};
+use std::sync::Arc;
+
+/// Three-dimensional checkerboard of unit cubes alternating between two patterns.
+#[derive(Debug, Clone)]
+pub struct CheckerPattern {
+    pub transform: Transform,
+    pub a: Arc<dyn Pattern>,
+    pub b: Arc<dyn Pattern>,
+}
+
+impl CheckerPattern {
+    pub fn new(a: Color, b: Color) -> Self {
+        CheckerPattern {
+            transform: Transform::identity(),
+            a: Arc::new(SolidPattern::new(a)),
+            b: Arc::new(SolidPattern::new(b)),
+        }
+    }
+}
+
+impl Pattern for CheckerPattern {
+    fn transform(&self) -> &Transform {
+        &self.transform
+    }
+
+    fn local_pattern_at(&self, point: Point) -> Color {
+        let sum = point.x.floor() + point.y.floor() + point.z.floor();
+        if sum.rem_euclid(2.0) == 0.0 {
+            self.a.pattern_at(point)
+        } else {
+            self.b.pattern_at(point)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Float;
+
+    fn point(x: Float, y: Float, z: Float) -> Point {
+        Point { x, y, z }
+    }
+
+    #[test]
+    fn checkers_repeat_in_x() {
+        let pattern = CheckerPattern::new(Color::white(), Color::black());
+        assert_eq!(pattern.pattern_at(point(0.0, 0.0, 0.0)), Color::white());
+        assert_eq!(pattern.pattern_at(point(0.99, 0.0, 0.0)), Color::white());
+        assert_eq!(pattern.pattern_at(point(1.01, 0.0, 0.0)), Color::black());
+    }
+
+    #[test]
+    fn checkers_repeat_in_y() {
+        let pattern = CheckerPattern::new(Color::white(), Color::black());
+        assert_eq!(pattern.pattern_at(point(0.0, 0.0, 0.0)), Color::white());
+        assert_eq!(pattern.pattern_at(point(0.0, 0.99, 0.0)), Color::white());
+        assert_eq!(pattern.pattern_at(point(0.0, 1.01, 0.0)), Color::black());
+    }
+
+    #[test]
+    fn checkers_repeat_in_z() {
+        let pattern = CheckerPattern::new(Color::white(), Color::black());
+        assert_eq!(pattern.pattern_at(point(0.0, 0.0, 0.0)), Color::white());
+        assert_eq!(pattern.pattern_at(point(0.0, 0.0, 0.99)), Color::white());
+        assert_eq!(pattern.pattern_at(point(0.0, 0.0, 1.01)), Color::black());
+    }
+
+    #[test]
+    fn checkers_alternate_for_negative_coordinates() {
+        let pattern = CheckerPattern::new(Color::white(), Color::black());
+        assert_eq!(pattern.pattern_at(point(-0.5, 0.0, 0.0)), Color::black());
+        assert_eq!(pattern.pattern_at(point(-0.5, -0.5, 0.0)), Color::white());
+    }
+}
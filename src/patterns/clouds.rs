@@ -0,0 +1,76 @@
+use crate::{Color, Pattern, Perlin, Point, SolidPattern, Transform};
+use std::sync::Arc;
+
+/// Fractal noise blending between the sky `a` and the clouds `b`.
+#[derive(Debug, Clone)]
+pub struct CloudsPattern {
+    pub transform: Transform,
+    pub a: Arc<dyn Pattern>,
+    pub b: Arc<dyn Pattern>,
+    pub noise: Perlin,
+    pub octaves: usize,
+}
+
+impl CloudsPattern {
+    pub fn new(a: Color, b: Color, seed: u64) -> Self {
+        CloudsPattern {
+            transform: Transform::identity(),
+            a: Arc::new(SolidPattern::new(a)),
+            b: Arc::new(SolidPattern::new(b)),
+            noise: Perlin::new(seed),
+            octaves: 5,
+        }
+    }
+}
+
+impl Pattern for CloudsPattern {
+    fn transform(&self) -> &Transform {
+        &self.transform
+    }
+
+    fn local_pattern_at(&self, point: Point) -> Color {
+        let t = (0.5 + 0.5 * self.noise.fbm(point, self.octaves)).clamp(0.0, 1.0);
+        let a = self.a.pattern_at(point);
+        a + (self.b.pattern_at(point) - a) * t
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Float;
+
+    fn point(x: Float, y: Float, z: Float) -> Point {
+        Point { x, y, z }
+    }
+
+    fn gray(value: Float) -> Color {
+        Color {
+            r: value,
+            g: value,
+            b: value,
+        }
+    }
+
+    #[test]
+    fn clouds_are_halfway_at_integer_coordinates() {
+        // All octaves of the noise vanish there.
+        let pattern = CloudsPattern::new(Color::white(), Color::black(), 9);
+        assert_eq!(pattern.pattern_at(point(0.0, 0.0, 0.0)), gray(0.5));
+        assert_eq!(pattern.pattern_at(point(3.0, -1.0, 7.0)), gray(0.5));
+    }
+
+    #[test]
+    fn clouds_depend_on_seed() {
+        let p = point(0.4, 1.7, -0.3);
+        let pattern = CloudsPattern::new(Color::white(), Color::black(), 1);
+        assert_eq!(
+            pattern.pattern_at(p),
+            CloudsPattern::new(Color::white(), Color::black(), 1).pattern_at(p)
+        );
+        assert_ne!(
+            pattern.pattern_at(p),
+            CloudsPattern::new(Color::white(), Color::black(), 2).pattern_at(p)
+        );
+    }
+}
@@ -0,0 +1,66 @@
+use crate::{Color, Pattern, Point, SolidPattern, Transform};
+use std::sync::Arc;
+
+/// Linearly interpolates from `a` at x = 0 to `b` at x = 1, repeating every unit.
+#[derive(Debug, Clone)]
+pub struct GradientPattern {
+    pub transform: Transform,
+    pub a: Arc<dyn Pattern>,
+    pub b: Arc<dyn Pattern>,
+}
+
+impl GradientPattern {
+    pub fn new(a: Color, b: Color) -> Self {
+        GradientPattern {
+            transform: Transform::identity(),
+            a: Arc::new(SolidPattern::new(a)),
+            b: Arc::new(SolidPattern::new(b)),
+        }
+    }
+}
+
+impl Pattern for GradientPattern {
+    fn transform(&self) -> &Transform {
+        &self.transform
+    }
+
+    fn local_pattern_at(&self, point: Point) -> Color {
+        let a = self.a.pattern_at(point);
+        let b = self.b.pattern_at(point);
+        a + (b - a) * (point.x - point.x.floor())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Float, Matrix4x4};
+
+    fn point(x: Float, y: Float, z: Float) -> Point {
+        Point { x, y, z }
+    }
+
+    #[test]
+    fn gradient_interpolates_between_colors() {
+        let pattern = GradientPattern::new(Color::white(), Color::black());
+        let color = |value| Color {
+            r: value,
+            g: value,
+            b: value,
+        };
+        assert_eq!(pattern.pattern_at(point(0.0, 0.0, 0.0)), color(1.0));
+        assert_eq!(pattern.pattern_at(point(0.25, 0.0, 0.0)), color(0.75));
+        assert_eq!(pattern.pattern_at(point(0.5, 0.0, 0.0)), color(0.5));
+        assert_eq!(pattern.pattern_at(point(0.75, 0.0, 0.0)), color(0.25));
+        assert_eq!(pattern.pattern_at(point(1.25, 0.0, 0.0)), color(0.75));
+    }
+
+    #[test]
+    fn gradient_with_pattern_transform() {
+        let pattern = GradientPattern {
+            transform: Transform::new(Matrix4x4::scaling(4.0, 1.0, 1.0)).unwrap(),
+            ..GradientPattern::new(Color::white(), Color::black())
+        };
+        assert_eq!(pattern.pattern_at(point(2.0, 0.0, 0.0)), Color::gray());
+    }
+}
@@ -0,0 +1,89 @@
+use crate::{Color, Float, Pattern, Perlin, Point, SolidPattern, Transform};
+use std::sync::Arc;
+
+/// Veins parallel to the yz plane, blending from `a` to `b` and back every two
+/// units along x and distorted by turbulence.
+#[derive(Debug, Clone)]
+pub struct MarblePattern {
+    pub transform: Transform,
+    pub a: Arc<dyn Pattern>,
+    pub b: Arc<dyn Pattern>,
+    pub noise: Perlin,
+    pub octaves: usize,
+    /// How strongly the veins are distorted, 0 gives straight veins.
+    pub turbulence: Float,
+}
+
+impl MarblePattern {
+    pub fn new(a: Color, b: Color, seed: u64) -> Self {
+        MarblePattern {
+            transform: Transform::identity(),
+            a: Arc::new(SolidPattern::new(a)),
+            b: Arc::new(SolidPattern::new(b)),
+            noise: Perlin::new(seed),
+            octaves: 6,
+            turbulence: 4.0,
+        }
+    }
+}
+
+impl Pattern for MarblePattern {
+    fn transform(&self) -> &Transform {
+        &self.transform
+    }
+
+    fn local_pattern_at(&self, point: Point) -> Color {
+        let distortion = self.turbulence * self.noise.turbulence(point, self.octaves);
+        let t = 0.5 + 0.5 * Float::sin((point.x + distortion) * std::f64::consts::PI as Float);
+        let a = self.a.pattern_at(point);
+        a + (self.b.pattern_at(point) - a) * t
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::approx_eq;
+
+    fn point(x: Float, y: Float, z: Float) -> Point {
+        Point { x, y, z }
+    }
+
+    fn gray(value: Float) -> Color {
+        Color {
+            r: value,
+            g: value,
+            b: value,
+        }
+    }
+
+    #[test]
+    fn marble_without_turbulence_is_periodic_in_x() {
+        let pattern = MarblePattern {
+            turbulence: 0.0,
+            ..MarblePattern::new(Color::white(), Color::black(), 0)
+        };
+        let assert_gray = |x, value| {
+            let color = pattern.pattern_at(point(x, 0.3, 0.7));
+            assert!(approx_eq!(color.r, value));
+            assert!(approx_eq!(color.g, value));
+            assert!(approx_eq!(color.b, value));
+        };
+        assert_gray(0.0, 0.5);
+        assert_gray(0.5, 0.0);
+        assert_gray(1.0, 0.5);
+        assert_gray(1.5, 1.0);
+        assert_gray(2.5, 0.0);
+    }
+
+    #[test]
+    fn marble_stays_between_colors() {
+        let pattern = MarblePattern::new(Color::white(), Color::black(), 5);
+        for i in 0..100 {
+            let i = i as Float;
+            let color = pattern.pattern_at(point(i * 0.173, i * 0.071, -i * 0.029));
+            assert!((0.0..=1.0).contains(&color.r));
+            assert_eq!(color, gray(color.r));
+        }
+    }
+}
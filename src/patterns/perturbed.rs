@@ -0,0 +1,70 @@
+use crate::{Color, Float, Pattern, Perlin, Point, Transform};
+use std::sync::Arc;
+
+/// Displaces the points passed to another pattern using noise, which makes
+/// regular patterns look more natural.
+#[derive(Debug, Clone)]
+pub struct PerturbedPattern {
+    pub transform: Transform,
+    pub pattern: Arc<dyn Pattern>,
+    pub noise: Perlin,
+    /// Maximum displacement in every direction.
+    pub scale: Float,
+}
+
+impl PerturbedPattern {
+    pub fn new(pattern: Arc<dyn Pattern>, seed: u64) -> Self {
+        PerturbedPattern {
+            transform: Transform::identity(),
+            pattern,
+            noise: Perlin::new(seed),
+            scale: 0.2,
+        }
+    }
+}
+
+impl Pattern for PerturbedPattern {
+    fn transform(&self) -> &Transform {
+        &self.transform
+    }
+
+    fn local_pattern_at(&self, point: Point) -> Color {
+        let displacement = self.noise.noise_vector(point) * self.scale;
+        self.pattern.pattern_at(point + displacement)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GradientPattern;
+
+    fn point(x: Float, y: Float, z: Float) -> Point {
+        Point { x, y, z }
+    }
+
+    #[test]
+    fn perturbed_pattern_without_displacement() {
+        let gradient = Arc::new(GradientPattern::new(Color::white(), Color::black()));
+        let pattern = PerturbedPattern {
+            scale: 0.0,
+            ..PerturbedPattern::new(gradient.clone(), 0)
+        };
+        for x in [0.1, 0.4, 0.7] {
+            let p = point(x, 0.3, -0.6);
+            assert_eq!(pattern.pattern_at(p), gradient.pattern_at(p));
+        }
+    }
+
+    #[test]
+    fn perturbed_pattern_displaces_points() {
+        let gradient = Arc::new(GradientPattern::new(Color::white(), Color::black()));
+        let pattern = PerturbedPattern::new(gradient.clone(), 0);
+        let p = point(0.3, 0.6, 0.2);
+        let color = pattern.pattern_at(p);
+        assert_ne!(color, gradient.pattern_at(p));
+        // Displacing by at most 0.2 changes the gradient by at most 0.2.
+        assert!((color.r - gradient.pattern_at(p).r).abs() <= 0.2);
+        assert_eq!(color, PerturbedPattern::new(gradient, 0).pattern_at(p));
+    }
+}
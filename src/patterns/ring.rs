@@ -0,0 +1,55 @@
+use crate::{Color, Float, Pattern, Point, SolidPattern, Transform};
+use std::sync::Arc;
+
+/// Concentric rings of width 1 around the y axis, alternating between two patterns.
+#[derive(Debug, Clone)]
+pub struct RingPattern {
+    pub transform: Transform,
+    pub a: Arc<dyn Pattern>,
+    pub b: Arc<dyn Pattern>,
+}
+
+impl RingPattern {
+    pub fn new(a: Color, b: Color) -> Self {
+        RingPattern {
+            transform: Transform::identity(),
+            a: Arc::new(SolidPattern::new(a)),
+            b: Arc::new(SolidPattern::new(b)),
+        }
+    }
+}
+
+impl Pattern for RingPattern {
+    fn transform(&self) -> &Transform {
+        &self.transform
+    }
+
+    fn local_pattern_at(&self, point: Point) -> Color {
+        let distance = Float::sqrt(point.x * point.x + point.z * point.z);
+        if distance.floor().rem_euclid(2.0) == 0.0 {
+            self.a.pattern_at(point)
+        } else {
+            self.b.pattern_at(point)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(x: Float, y: Float, z: Float) -> Point {
+        Point { x, y, z }
+    }
+
+    #[test]
+    fn ring_extends_in_x_and_z() {
+        let pattern = RingPattern::new(Color::white(), Color::black());
+        assert_eq!(pattern.pattern_at(point(0.0, 0.0, 0.0)), Color::white());
+        assert_eq!(pattern.pattern_at(point(1.0, 0.0, 0.0)), Color::black());
+        assert_eq!(pattern.pattern_at(point(0.0, 0.0, 1.0)), Color::black());
+        // Slightly more than sqrt(2) / 2 in both x and z.
+        assert_eq!(pattern.pattern_at(point(0.708, 0.0, 0.708)), Color::black());
+        assert_eq!(pattern.pattern_at(point(0.0, 5.0, 2.5)), Color::white());
+    }
+}
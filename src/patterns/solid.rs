@@ -0,0 +1,46 @@
+use crate::{Color, Pattern, Point, Transform};
+
+/// Pattern with a single color everywhere, mostly useful as part of other patterns.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct SolidPattern {
+    pub transform: Transform,
+    pub color: Color,
+}
+
+impl SolidPattern {
+    pub fn new(color: Color) -> Self {
+        SolidPattern {
+            transform: Transform::identity(),
+            color,
+        }
+    }
+}
+
+impl Pattern for SolidPattern {
+    fn transform(&self) -> &Transform {
+        &self.transform
+    }
+
+    fn local_pattern_at(&self, _point: Point) -> Color {
+        self.color
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solid_pattern_is_constant() {
+        let pattern = SolidPattern::new(Color::red());
+        assert_eq!(pattern.pattern_at(Point::origin()), Color::red());
+        assert_eq!(
+            pattern.pattern_at(Point {
+                x: -3.5,
+                y: 7.0,
+                z: 0.25,
+            }),
+            Color::red()
+        );
+    }
+}
@@ -0,0 +1,99 @@
+use crate::{Color, Pattern, Point, SolidPattern, Transform};
+use std::sync::Arc;
+
+/// Alternates between two patterns in stripes of width 1 along the x axis.
+#[derive(Debug, Clone)]
+pub struct StripePattern {
+    pub transform: Transform,
+    pub a: Arc<dyn Pattern>,
+    pub b: Arc<dyn Pattern>,
+}
+
+impl StripePattern {
+    pub fn new(a: Color, b: Color) -> Self {
+        StripePattern {
+            transform: Transform::identity(),
+            a: Arc::new(SolidPattern::new(a)),
+            b: Arc::new(SolidPattern::new(b)),
+        }
+    }
+}
+
+impl Pattern for StripePattern {
+    fn transform(&self) -> &Transform {
+        &self.transform
+    }
+
+    fn local_pattern_at(&self, point: Point) -> Color {
+        if point.x.floor().rem_euclid(2.0) == 0.0 {
+            self.a.pattern_at(point)
+        } else {
+            self.b.pattern_at(point)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Float, Matrix4x4};
+
+    fn point(x: Float, y: Float, z: Float) -> Point {
+        Point { x, y, z }
+    }
+
+    #[test]
+    fn stripe_pattern_is_constant_in_y() {
+        let pattern = StripePattern::new(Color::white(), Color::black());
+        assert_eq!(pattern.pattern_at(point(0.0, 0.0, 0.0)), Color::white());
+        assert_eq!(pattern.pattern_at(point(0.0, 1.0, 0.0)), Color::white());
+        assert_eq!(pattern.pattern_at(point(0.0, 2.0, 0.0)), Color::white());
+    }
+
+    #[test]
+    fn stripe_pattern_is_constant_in_z() {
+        let pattern = StripePattern::new(Color::white(), Color::black());
+        assert_eq!(pattern.pattern_at(point(0.0, 0.0, 0.0)), Color::white());
+        assert_eq!(pattern.pattern_at(point(0.0, 0.0, 1.0)), Color::white());
+        assert_eq!(pattern.pattern_at(point(0.0, 0.0, 2.0)), Color::white());
+    }
+
+    #[test]
+    fn stripe_pattern_alternates_in_x() {
+        let pattern = StripePattern::new(Color::white(), Color::black());
+        assert_eq!(pattern.pattern_at(point(0.0, 0.0, 0.0)), Color::white());
+        assert_eq!(pattern.pattern_at(point(0.9, 0.0, 0.0)), Color::white());
+        assert_eq!(pattern.pattern_at(point(1.0, 0.0, 0.0)), Color::black());
+        assert_eq!(pattern.pattern_at(point(-0.1, 0.0, 0.0)), Color::black());
+        assert_eq!(pattern.pattern_at(point(-1.0, 0.0, 0.0)), Color::black());
+        assert_eq!(pattern.pattern_at(point(-1.1, 0.0, 0.0)), Color::white());
+    }
+
+    #[test]
+    fn stripes_with_pattern_transform() {
+        let pattern = StripePattern {
+            transform: Transform::new(Matrix4x4::scaling(2.0, 2.0, 2.0)).unwrap(),
+            ..StripePattern::new(Color::white(), Color::black())
+        };
+        assert_eq!(pattern.pattern_at(point(1.5, 0.0, 0.0)), Color::white());
+        assert_eq!(pattern.pattern_at(point(2.5, 0.0, 0.0)), Color::black());
+    }
+
+    #[test]
+    fn nested_patterns_use_their_own_transform() {
+        // Stripes along z nested in the white stripes along x.
+        let pattern = StripePattern {
+            a: Arc::new(StripePattern {
+                transform: Transform::new(Matrix4x4::rotation_y(
+                    std::f64::consts::FRAC_PI_2 as Float,
+                ))
+                .unwrap(),
+                ..StripePattern::new(Color::red(), Color::green())
+            }),
+            ..StripePattern::new(Color::white(), Color::black())
+        };
+        assert_eq!(pattern.pattern_at(point(0.5, 0.0, -0.5)), Color::red());
+        assert_eq!(pattern.pattern_at(point(0.5, 0.0, 0.5)), Color::green());
+        assert_eq!(pattern.pattern_at(point(1.5, 0.0, 0.5)), Color::black());
+    }
+}
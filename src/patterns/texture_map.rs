@@ -0,0 +1,116 @@
+use crate::{Color, Float, Pattern, Point, Texture, Transform, UvMapping};
+use std::sync::Arc;
+
+/// Applies a two-dimensional texture to the surface using a `UvMapping`.
+#[derive(Debug, Clone)]
+pub struct TextureMapPattern {
+    pub transform: Transform,
+    pub mapping: UvMapping,
+    pub texture: Arc<dyn Texture>,
+}
+
+impl TextureMapPattern {
+    pub fn new(mapping: UvMapping, texture: Arc<dyn Texture>) -> Self {
+        TextureMapPattern {
+            transform: Transform::identity(),
+            mapping,
+            texture,
+        }
+    }
+}
+
+impl Pattern for TextureMapPattern {
+    fn transform(&self) -> &Transform {
+        &self.transform
+    }
+
+    fn local_pattern_at(&self, point: Point) -> Color {
+        let (u, v) = self.mapping.map(point);
+        self.texture.texture_at(u, v)
+    }
+
+    fn pattern_at_uv(&self, point: Point, texture_coordinates: Option<(Float, Float)>) -> Color {
+        match (self.mapping, texture_coordinates) {
+            (UvMapping::Surface, Some((u, v))) => self.texture.texture_at(u, v),
+            _ => self.pattern_at(point),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CheckerTexture, Matrix4x4};
+
+    #[test]
+    fn texture_map_with_spherical_mapping() {
+        let pattern = TextureMapPattern::new(
+            UvMapping::Spherical,
+            Arc::new(CheckerTexture {
+                width: 16,
+                height: 8,
+                a: Color::black(),
+                b: Color::white(),
+            }),
+        );
+        for ((x, y, z), color) in [
+            ((0.4315, 0.4670, 0.7719), Color::white()),
+            ((-0.9654, 0.2552, -0.0534), Color::black()),
+            ((0.1039, 0.7090, 0.6975), Color::white()),
+            ((-0.4986, -0.7856, -0.3663), Color::black()),
+            ((-0.0317, -0.9395, 0.3411), Color::black()),
+            ((0.4809, -0.7721, 0.4154), Color::black()),
+            ((0.0285, -0.9612, -0.2745), Color::black()),
+            ((-0.5734, -0.2162, -0.7903), Color::white()),
+            ((0.7688, -0.1470, 0.6223), Color::black()),
+            ((-0.7652, 0.2175, 0.6060), Color::black()),
+        ] {
+            assert_eq!(pattern.pattern_at(Point { x, y, z }), color);
+        }
+    }
+
+    #[test]
+    fn texture_map_with_pattern_transform() {
+        let pattern = TextureMapPattern {
+            transform: Transform::new(Matrix4x4::scaling(2.0, 2.0, 2.0)).unwrap(),
+            ..TextureMapPattern::new(
+                UvMapping::Planar,
+                Arc::new(CheckerTexture {
+                    width: 2,
+                    height: 2,
+                    a: Color::black(),
+                    b: Color::white(),
+                }),
+            )
+        };
+        let point = |x, z| Point { x, y: 0.0, z };
+        assert_eq!(pattern.pattern_at(point(0.5, 0.5)), Color::black());
+        assert_eq!(pattern.pattern_at(point(1.5, 0.5)), Color::white());
+        assert_eq!(pattern.pattern_at(point(1.5, 1.5)), Color::black());
+    }
+
+    #[test]
+    fn texture_map_with_surface_mapping() {
+        let pattern = TextureMapPattern::new(
+            UvMapping::Surface,
+            Arc::new(CheckerTexture {
+                width: 2,
+                height: 2,
+                a: Color::black(),
+                b: Color::white(),
+            }),
+        );
+        let point = Point {
+            x: 0.25,
+            y: 0.0,
+            z: 0.25,
+        };
+        assert_eq!(
+            pattern.pattern_at_uv(point, Some((0.75, 0.25))),
+            Color::white()
+        );
+        // Without texture coordinates, the point is mapped like with the planar mapping.
+        assert_eq!(pattern.pattern_at_uv(point, None), Color::black());
+        assert_eq!(pattern.pattern_at(point), Color::black());
+    }
+}
@@ -0,0 +1,81 @@
+use crate::{Color, Float, Pattern, Perlin, Point, SolidPattern, Transform};
+use std::sync::Arc;
+
+/// Growth rings of width 1 around the y axis, each blending from `a` to `b` and
+/// distorted by noise.
+#[derive(Debug, Clone)]
+pub struct WoodPattern {
+    pub transform: Transform,
+    pub a: Arc<dyn Pattern>,
+    pub b: Arc<dyn Pattern>,
+    pub noise: Perlin,
+    /// How strongly the rings are distorted, 0 gives perfect circles.
+    pub turbulence: Float,
+}
+
+impl WoodPattern {
+    pub fn new(a: Color, b: Color, seed: u64) -> Self {
+        WoodPattern {
+            transform: Transform::identity(),
+            a: Arc::new(SolidPattern::new(a)),
+            b: Arc::new(SolidPattern::new(b)),
+            noise: Perlin::new(seed),
+            turbulence: 0.3,
+        }
+    }
+}
+
+impl Pattern for WoodPattern {
+    fn transform(&self) -> &Transform {
+        &self.transform
+    }
+
+    fn local_pattern_at(&self, point: Point) -> Color {
+        let distance = Float::sqrt(point.x * point.x + point.z * point.z)
+            + self.turbulence * self.noise.noise(point);
+        let t = distance - distance.floor();
+        let a = self.a.pattern_at(point);
+        a + (self.b.pattern_at(point) - a) * t
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(x: Float, y: Float, z: Float) -> Point {
+        Point { x, y, z }
+    }
+
+    fn gray(value: Float) -> Color {
+        Color {
+            r: value,
+            g: value,
+            b: value,
+        }
+    }
+
+    #[test]
+    fn wood_without_turbulence_has_circular_rings() {
+        let pattern = WoodPattern {
+            turbulence: 0.0,
+            ..WoodPattern::new(Color::white(), Color::black(), 0)
+        };
+        assert_eq!(pattern.pattern_at(point(0.0, 0.0, 0.0)), gray(1.0));
+        assert_eq!(pattern.pattern_at(point(0.25, 3.0, 0.0)), gray(0.75));
+        assert_eq!(pattern.pattern_at(point(0.0, -2.0, 1.5)), gray(0.5));
+        assert_eq!(pattern.pattern_at(point(0.6, 0.0, 0.8)), gray(1.0));
+    }
+
+    #[test]
+    fn wood_rings_are_distorted_by_noise() {
+        let pattern = WoodPattern::new(Color::white(), Color::black(), 0);
+        let p = point(0.3, 0.45, 0.2);
+        let undistorted = WoodPattern {
+            turbulence: 0.0,
+            ..pattern.clone()
+        };
+        assert_ne!(pattern.pattern_at(p), undistorted.pattern_at(p));
+        assert_eq!(pattern.pattern_at(p), pattern.clone().pattern_at(p));
+    }
+}
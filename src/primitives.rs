@@ -2,4 +2,5 @@
 pub mod float;
 pub mod color;
 pub mod matrix;
+pub mod transform;
 pub mod tuple;
@@ -1,6 +1,14 @@
 // Choose between f32 and f64.
 pub type Float = f64;
 
+/// Distance by which points are moved off a surface before secondary rays are cast
+/// from them. Without it rounding errors make those rays hit the very surface they
+/// start on ("acne"). Derived from the machine epsilon, so it follows the choice of
+/// `Float`.
+pub fn epsilon() -> Float {
+    Float::EPSILON.cbrt()
+}
+
 #[cfg(test)]
 #[macro_export]
 macro_rules! approx_eq {
@@ -35,6 +43,14 @@ mod tests {
         assert!(approx_eq!(1.0 + 1e-3, 1.0, rtol = 1e-2, atol = 0.0));
     }
 
+    #[test]
+    fn epsilon_is_larger_than_rounding_errors() {
+        assert!(epsilon() > Float::EPSILON);
+        assert!(epsilon() < 1e-3);
+        // Moving a point of typical scene scale by epsilon must actually change it.
+        assert_ne!(100.0 + epsilon(), 100.0);
+    }
+
     #[test]
     #[should_panic]
     fn approx_eq_is_false_when_difference_exceeds_atol() {
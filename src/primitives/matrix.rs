@@ -1,4 +1,5 @@
 use super::float::Float;
+use crate::{Point, Vector};
 use std::fmt;
 use std::ops::{Index, IndexMut};
 
@@ -147,6 +148,23 @@ impl Matrix<4, 4> {
             ],
         }
     }
+
+    /// Transforms the world such that an eye at `from` looks towards `to`, with `up`
+    /// pointing (roughly) upwards.
+    pub fn view_transform(from: Point, to: Point, up: Vector) -> Self {
+        let forward = (to - from).normalize();
+        let left = forward.cross(up.normalize());
+        let true_up = left.cross(forward);
+        let orientation = Matrix {
+            data: [
+                [left.x, left.y, left.z, 0.0],
+                [true_up.x, true_up.y, true_up.z, 0.0],
+                [-forward.x, -forward.y, -forward.z, 0.0],
+                [0.0, 0.0, 0.0, 1.0],
+            ],
+        };
+        orientation.matmul(Matrix::translation(-from.x, -from.y, -from.z))
+    }
 }
 
 pub trait Invertible
@@ -246,7 +264,7 @@ impl Invertible for Matrix4x4 {
             let t0212 = self[1][0] * self[2][2] - self[1][2] * self[2][0];
             let t0113 = self[1][0] * self[3][1] - self[1][1] * self[3][0];
             let t0112 = self[1][0] * self[2][1] - self[1][1] * self[2][0];
-            Some(Matrix4x4::new([
+            let mut inverse = Matrix4x4::new([
                 [
                     inv_det * (self[1][1] * t2323 - self[1][2] * t1323 + self[1][3] * t1223),
                     inv_det * (self[0][2] * t1323 - self[0][1] * t2323 - self[0][3] * t1223),
@@ -271,7 +289,13 @@ impl Invertible for Matrix4x4 {
                     inv_det * (self[0][1] * t0213 - self[0][0] * t1213 - self[0][2] * t0113),
                     inv_det * (self[0][0] * t1212 - self[0][1] * t0212 + self[0][2] * t0112),
                 ],
-            ]))
+            ]);
+            // The inverse of an affine transform is affine, but rounding errors would
+            // give points and vectors a w slightly different from 1 and 0.
+            if self[3] == [0.0, 0.0, 0.0, 1.0] {
+                inverse[3] = [0.0, 0.0, 0.0, 1.0];
+            }
+            Some(inverse)
         } else {
             None
         }
@@ -318,7 +342,6 @@ impl<const M: usize, const N: usize> fmt::Display for Matrix<M, N> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{Point, Vector};
     use std::{f64::consts::FRAC_PI_2, iter::zip};
 
     macro_rules! assert_approx_eq {
@@ -662,6 +685,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn inverse_of_affine_matrix_keeps_w() {
+        // Without correction, the last row of the inverse ends in 0.9999999999999999.
+        let m = Matrix4x4::new([
+            [
+                0.0502860894571845,
+                0.0,
+                0.9756087151464411,
+                3.8647821376323854,
+            ],
+            [0.0, 0.9769038109571411, 0.0, -5.748277618575264],
+            [
+                -0.9756087151464411,
+                0.0,
+                0.0502860894571845,
+                9.57553730847241,
+            ],
+            [0.0, 0.0, 0.0, 1.0],
+        ]);
+        assert_eq!(m.inverse().unwrap()[3], [0.0, 0.0, 0.0, 1.0]);
+    }
+
     #[test]
     fn reflection_as_negative_scaling() {
         let s = Matrix::scaling(-1.0, 1.0, 1.0);
@@ -770,6 +815,96 @@ mod tests {
         assert_approx_eq!(rst.matmul(p), p4);
     }
 
+    #[test]
+    fn view_transform_for_default_orientation() {
+        let from = Point::origin();
+        let to = Point {
+            x: 0.0,
+            y: 0.0,
+            z: -1.0,
+        };
+        let up = Vector {
+            x: 0.0,
+            y: 1.0,
+            z: 0.0,
+        };
+        assert_eq!(
+            Matrix4x4::view_transform(from, to, up),
+            Matrix4x4::identity()
+        );
+    }
+
+    #[test]
+    fn view_transform_looking_in_positive_z_direction() {
+        let from = Point::origin();
+        let to = Point {
+            x: 0.0,
+            y: 0.0,
+            z: 1.0,
+        };
+        let up = Vector {
+            x: 0.0,
+            y: 1.0,
+            z: 0.0,
+        };
+        assert_eq!(
+            Matrix4x4::view_transform(from, to, up),
+            Matrix4x4::scaling(-1.0, 1.0, -1.0)
+        );
+    }
+
+    #[test]
+    fn view_transform_moves_the_world() {
+        let from = Point {
+            x: 0.0,
+            y: 0.0,
+            z: 8.0,
+        };
+        let to = Point::origin();
+        let up = Vector {
+            x: 0.0,
+            y: 1.0,
+            z: 0.0,
+        };
+        assert_eq!(
+            Matrix4x4::view_transform(from, to, up),
+            Matrix4x4::translation(0.0, 0.0, -8.0)
+        );
+    }
+
+    #[test]
+    fn arbitrary_view_transform() {
+        let from = Point {
+            x: 1.0,
+            y: 3.0,
+            z: 2.0,
+        };
+        let to = Point {
+            x: 4.0,
+            y: -2.0,
+            z: 8.0,
+        };
+        let up = Vector {
+            x: 1.0,
+            y: 1.0,
+            z: 0.0,
+        };
+        let t = Matrix4x4::view_transform(from, to, up);
+        for (row1, row2) in zip(
+            t.data,
+            [
+                [-0.50709, 0.50709, 0.67612, -2.36643],
+                [0.76772, 0.60609, 0.12122, -2.82843],
+                [-0.35857, 0.59761, -0.71714, 0.0],
+                [0.0, 0.0, 0.0, 1.0],
+            ],
+        ) {
+            for (x1, x2) in zip(row1, row2) {
+                assert!(approx_eq!(x1, x2, atol = 1e-5));
+            }
+        }
+    }
+
     #[test]
     fn matrix_to_string() {
         let m = Matrix::<3, 2>::new([[0.1, -1.0], [2.0, 3.09], [-4.0, 5.0]]);
@@ -0,0 +1,119 @@
+use crate::{Invertible, Matrix4x4};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum TransformError {
+    #[error("transform is not invertible:\n{0}")]
+    NotInvertible(String),
+    #[error("transform is not affine:\n{0}")]
+    NotAffine(String),
+}
+
+/// Transformation matrix together with its inverse and inverse-transpose.
+///
+/// Both are computed once on construction, which fails for matrices that cannot
+/// be inverted. Only affine matrices are accepted, as others would not map points
+/// to points and vectors to vectors.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Transform {
+    matrix: Matrix4x4,
+    inverse: Matrix4x4,
+    inverse_transpose: Matrix4x4,
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Transform::identity()
+    }
+}
+
+impl Transform {
+    pub fn new(matrix: Matrix4x4) -> Result<Self, TransformError> {
+        if matrix[3] != [0.0, 0.0, 0.0, 1.0] {
+            return Err(TransformError::NotAffine(matrix.to_string()));
+        }
+        let inverse = matrix
+            .inverse()
+            .ok_or_else(|| TransformError::NotInvertible(matrix.to_string()))?;
+        Ok(Transform {
+            matrix,
+            inverse,
+            inverse_transpose: inverse.transpose(),
+        })
+    }
+
+    pub fn identity() -> Self {
+        Transform {
+            matrix: Matrix4x4::identity(),
+            inverse: Matrix4x4::identity(),
+            inverse_transpose: Matrix4x4::identity(),
+        }
+    }
+
+    pub fn matrix(&self) -> &Matrix4x4 {
+        &self.matrix
+    }
+
+    pub fn inverse(&self) -> &Matrix4x4 {
+        &self.inverse
+    }
+
+    pub fn inverse_transpose(&self) -> &Matrix4x4 {
+        &self.inverse_transpose
+    }
+}
+
+impl TryFrom<Matrix4x4> for Transform {
+    type Error = TransformError;
+
+    fn try_from(matrix: Matrix4x4) -> Result<Self, Self::Error> {
+        Transform::new(matrix)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identity_transform() {
+        let transform = Transform::default();
+        assert_eq!(transform, Transform::identity());
+        assert_eq!(transform.matrix(), &Matrix4x4::identity());
+        assert_eq!(transform.inverse(), &Matrix4x4::identity());
+        assert_eq!(transform.inverse_transpose(), &Matrix4x4::identity());
+    }
+
+    #[test]
+    fn transform_stores_inverse_and_inverse_transpose() {
+        let matrix =
+            Matrix4x4::translation(1.0, 2.0, 3.0).matmul(Matrix4x4::scaling(2.0, 4.0, 8.0));
+        let transform = Transform::new(matrix).unwrap();
+        let inverse =
+            Matrix4x4::scaling(0.5, 0.25, 0.125).matmul(Matrix4x4::translation(-1.0, -2.0, -3.0));
+        assert_eq!(transform.matrix(), &matrix);
+        assert_eq!(transform.inverse(), &inverse);
+        assert_eq!(transform.inverse_transpose(), &inverse.transpose());
+    }
+
+    #[test]
+    fn reject_non_invertible_matrix() {
+        let matrix = Matrix4x4::scaling(1.0, 0.0, 1.0);
+        let e = Transform::try_from(matrix).unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            format!("transform is not invertible:\n{}", matrix)
+        );
+    }
+
+    #[test]
+    fn reject_non_affine_matrix() {
+        let mut matrix = Matrix4x4::identity();
+        matrix[3][0] = 1.0;
+        let e = Transform::new(matrix).unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            format!("transform is not affine:\n{}", matrix)
+        );
+    }
+}
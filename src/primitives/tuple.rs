@@ -3,8 +3,6 @@ use crate::Matrix;
 use std::fmt;
 use std::ops::{Add, Div, Mul, Neg, Sub};
 
-trait Tuple {}
-
 macro_rules! impl_tuple {
     ($name:ident, $w:literal) => {
         #[derive(Debug, PartialEq, Clone, Copy)]
@@ -56,8 +54,6 @@ macro_rules! impl_tuple {
                 }
             }
         }
-
-        impl Tuple for $name {}
     };
 }
 impl_tuple!(Point, 1.0);
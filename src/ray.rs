@@ -1,7 +1,7 @@
 use crate::primitives::float::Float;
-use crate::{Intersection, Invertible, Matrix4x4, Point, Sphere, Vector};
+use crate::{Intersection, Matrix4x4, Point, Shape, Vector};
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Ray {
     pub origin: Point,
     pub direction: Vector,
@@ -25,43 +25,17 @@ impl Ray {
         }
     }
 
-    // TODO: Later this function should work with more things than spheres.
-    pub fn intersect(&self, object: Sphere) -> Vec<Intersection> {
-        let inverse_transform = match object.transform.inverse() {
-            Some(m) => m,
-            None => return vec![],
-        };
-        let ray = self.transform(inverse_transform);
-        let sphere_to_ray = ray.origin - Point::origin();
-        let a = ray.direction.squared_length();
-        let b = 2.0 * ray.direction.dot(sphere_to_ray);
-        let c = sphere_to_ray.squared_length() - 1.0;
-        let discriminant = b * b - 4.0 * a * c;
-        if discriminant < 0.0 {
-            vec![]
-        } else {
-            let sqrt = Float::sqrt(discriminant);
-            let div = 1.0 / (2.0 * a);
-            vec![
-                Intersection {
-                    t: (-b - sqrt) * div,
-                    object,
-                },
-                Intersection {
-                    t: (-b + sqrt) * div,
-                    object,
-                },
-            ]
-        }
+    pub fn intersect<'a>(&self, object: &'a dyn Shape) -> Vec<Intersection<'a>> {
+        object.local_intersect(&self.transform(*object.transform().inverse()))
     }
 }
 
-pub fn get_hit(intersections: &[Intersection]) -> Option<Intersection> {
+pub fn get_hit<'a>(intersections: &[Intersection<'a>]) -> Option<Intersection<'a>> {
     let mut hit = None;
     let mut min_t = Float::INFINITY;
     for intersection in intersections {
         if intersection.t > 0.0 && intersection.t < min_t {
-            hit = Some(*intersection);
+            hit = Some(intersection.clone());
             min_t = intersection.t;
         }
     }
@@ -71,7 +45,7 @@ pub fn get_hit(intersections: &[Intersection]) -> Option<Intersection> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::Material;
+    use crate::{Material, Sphere, Transform};
 
     #[test]
     fn create_ray() {
@@ -221,8 +195,9 @@ mod tests {
             },
         };
         let sphere = Sphere::default();
-        let intersections = ray.intersect(sphere);
+        let intersections = ray.intersect(&sphere);
         assert_eq!(intersections.len(), 2);
+        let sphere: &dyn Shape = &sphere;
         assert_eq!(intersections.first().unwrap().object, sphere);
         assert_eq!(intersections.last().unwrap().object, sphere);
     }
@@ -242,7 +217,7 @@ mod tests {
             },
         };
         let sphere = Sphere::default();
-        let intersections = ray.intersect(sphere);
+        let intersections = ray.intersect(&sphere);
         assert_eq!(intersections.len(), 2);
         assert_eq!(intersections.first().unwrap().t, 4.0);
         assert_eq!(intersections.last().unwrap().t, 6.0);
@@ -263,7 +238,7 @@ mod tests {
             },
         };
         let sphere = Sphere::default();
-        let intersections = ray.intersect(sphere);
+        let intersections = ray.intersect(&sphere);
         assert_eq!(intersections.len(), 2);
         assert_eq!(intersections.first().unwrap().t, 5.0);
         assert_eq!(intersections.last().unwrap().t, 5.0);
@@ -284,7 +259,7 @@ mod tests {
             },
         };
         let sphere = Sphere::default();
-        let intersections = ray.intersect(sphere);
+        let intersections = ray.intersect(&sphere);
         assert_eq!(intersections.len(), 0);
     }
 
@@ -303,7 +278,7 @@ mod tests {
             },
         };
         let sphere = Sphere::default();
-        let intersections = ray.intersect(sphere);
+        let intersections = ray.intersect(&sphere);
         assert_eq!(intersections.len(), 2);
         assert_eq!(intersections.first().unwrap().t, -1.0);
         assert_eq!(intersections.last().unwrap().t, 1.0);
@@ -324,7 +299,7 @@ mod tests {
             },
         };
         let sphere = Sphere::default();
-        let intersections = ray.intersect(sphere);
+        let intersections = ray.intersect(&sphere);
         assert_eq!(intersections.len(), 2);
         assert_eq!(intersections.first().unwrap().t, -6.0);
         assert_eq!(intersections.last().unwrap().t, -4.0);
@@ -345,10 +320,10 @@ mod tests {
             },
         };
         let sphere = Sphere {
-            transform: Matrix4x4::scaling(2.0, 2.0, 2.0),
+            transform: Transform::new(Matrix4x4::scaling(2.0, 2.0, 2.0)).unwrap(),
             material: Material::default(),
         };
-        let intersections = ray.intersect(sphere);
+        let intersections = ray.intersect(&sphere);
         assert_eq!(intersections.len(), 2);
         assert_eq!(intersections.first().unwrap().t, 3.0);
         assert_eq!(intersections.last().unwrap().t, 7.0);
@@ -369,19 +344,19 @@ mod tests {
             },
         };
         let sphere = Sphere {
-            transform: Matrix4x4::translation(5.0, 0.0, 0.0),
+            transform: Transform::new(Matrix4x4::translation(5.0, 0.0, 0.0)).unwrap(),
             material: Material::default(),
         };
-        let intersections = ray.intersect(sphere);
+        let intersections = ray.intersect(&sphere);
         assert_eq!(intersections.len(), 0);
     }
 
     #[test]
     fn get_hit_when_all_intersections_have_positive_t() {
         let s = Sphere::default();
-        let i1 = Intersection { t: 1.0, object: s };
-        let i2 = Intersection { t: 2.0, object: s };
-        let xs = vec![i1, i2];
+        let i1 = Intersection::new(1.0, &s);
+        let i2 = Intersection::new(2.0, &s);
+        let xs = vec![i1.clone(), i2];
         let i = get_hit(&xs);
         assert_eq!(i, Some(i1));
     }
@@ -389,9 +364,9 @@ mod tests {
     #[test]
     fn get_hit_when_some_intersections_have_negative_t() {
         let s = Sphere::default();
-        let i1 = Intersection { t: -1.0, object: s };
-        let i2 = Intersection { t: 1.0, object: s };
-        let xs = vec![i1, i2];
+        let i1 = Intersection::new(-1.0, &s);
+        let i2 = Intersection::new(1.0, &s);
+        let xs = vec![i1, i2.clone()];
         let i = get_hit(&xs);
         assert_eq!(i, Some(i2));
     }
@@ -399,8 +374,8 @@ mod tests {
     #[test]
     fn get_hit_when_all_intersections_have_negative_t() {
         let s = Sphere::default();
-        let i1 = Intersection { t: -2.0, object: s };
-        let i2 = Intersection { t: -1.0, object: s };
+        let i1 = Intersection::new(-2.0, &s);
+        let i2 = Intersection::new(-1.0, &s);
         let xs = vec![i1, i2];
         let i = get_hit(&xs);
         assert_eq!(i, None);
@@ -409,11 +384,11 @@ mod tests {
     #[test]
     fn get_hit_is_always_lowest_non_negative_t() {
         let s = Sphere::default();
-        let i1 = Intersection { t: 5.0, object: s };
-        let i2 = Intersection { t: 7.0, object: s };
-        let i3 = Intersection { t: -3.0, object: s };
-        let i4 = Intersection { t: 2.0, object: s };
-        let xs = vec![i1, i2, i3, i4];
+        let i1 = Intersection::new(5.0, &s);
+        let i2 = Intersection::new(7.0, &s);
+        let i3 = Intersection::new(-3.0, &s);
+        let i4 = Intersection::new(2.0, &s);
+        let xs = vec![i1, i2, i3, i4.clone()];
         let i = get_hit(&xs);
         assert_eq!(i, Some(i4));
     }
@@ -0,0 +1,261 @@
+use crate::{Canvas, Color, Float, TransferFunction};
+use std::fmt::Debug;
+use std::sync::Arc;
+
+/// Two-dimensional color source, sampled with texture coordinates (u, v) where
+/// (0, 0) is the lower left and (1, 1) the upper right corner.
+pub trait Texture: Debug + Send + Sync {
+    fn texture_at(&self, u: Float, v: Float) -> Color;
+}
+
+/// How an `ImageTexture` is sampled between pixel centers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TextureFilter {
+    /// Color of the closest pixel.
+    Nearest,
+    /// Linear interpolation between the four closest pixels.
+    #[default]
+    Bilinear,
+}
+
+/// How an `ImageTexture` is sampled outside of [0, 1].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TextureWrap {
+    /// The image repeats.
+    #[default]
+    Wrap,
+    /// The border pixels extend infinitely.
+    Clamp,
+}
+
+impl TextureWrap {
+    fn pixel_index(&self, index: i64, size: usize) -> usize {
+        match self {
+            TextureWrap::Wrap => index.rem_euclid(size as i64) as usize,
+            TextureWrap::Clamp => index.clamp(0, size as i64 - 1) as usize,
+        }
+    }
+}
+
+/// Texture sampling an image, which is shared since images can be large.
+#[derive(Debug, Clone)]
+pub struct ImageTexture {
+    pub image: Arc<Canvas>,
+    pub filter: TextureFilter,
+    pub wrap: TextureWrap,
+}
+
+impl ImageTexture {
+    pub fn new(image: Arc<Canvas>) -> Self {
+        ImageTexture {
+            image,
+            filter: TextureFilter::default(),
+            wrap: TextureWrap::default(),
+        }
+    }
+
+    /// Creates a texture from an image stored with a transfer function, such as
+    /// sRGB for most image files, so that it is sampled with linear colors.
+    pub fn decoded(image: &Canvas, transfer: TransferFunction) -> Self {
+        ImageTexture::new(Arc::new(image.decode(transfer)))
+    }
+
+    fn pixel(&self, x: i64, y: i64) -> Color {
+        let x = self.wrap.pixel_index(x, self.image.width());
+        let y = self.wrap.pixel_index(y, self.image.height());
+        self.image.get_pixel(x, y).expect("indices should be valid")
+    }
+}
+
+impl Texture for ImageTexture {
+    fn texture_at(&self, u: Float, v: Float) -> Color {
+        let (width, height) = (self.image.width(), self.image.height());
+        if width == 0 || height == 0 {
+            return Color::black();
+        }
+        // Continuous pixel coordinates, the first row of the image is at the top.
+        let x = u * width as Float;
+        let y = (1.0 - v) * height as Float;
+        match self.filter {
+            TextureFilter::Nearest => self.pixel(x.floor() as i64, y.floor() as i64),
+            TextureFilter::Bilinear => {
+                // Relative to the pixel centers.
+                let (x, y) = (x - 0.5, y - 0.5);
+                let (x0, y0) = (x.floor(), y.floor());
+                let (tx, ty) = (x - x0, y - y0);
+                let (x0, y0) = (x0 as i64, y0 as i64);
+                let top = self.pixel(x0, y0) * (1.0 - tx) + self.pixel(x0 + 1, y0) * tx;
+                let bottom = self.pixel(x0, y0 + 1) * (1.0 - tx) + self.pixel(x0 + 1, y0 + 1) * tx;
+                top * (1.0 - ty) + bottom * ty
+            }
+        }
+    }
+}
+
+/// Checkerboard with `width` times `height` squares, useful for checking mappings.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CheckerTexture {
+    pub width: usize,
+    pub height: usize,
+    pub a: Color,
+    pub b: Color,
+}
+
+impl Texture for CheckerTexture {
+    fn texture_at(&self, u: Float, v: Float) -> Color {
+        let u = (u * self.width as Float).floor();
+        let v = (v * self.height as Float).floor();
+        if (u + v).rem_euclid(2.0) == 0.0 {
+            self.a
+        } else {
+            self.b
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::approx_eq;
+
+    // Image with red and green in the top row, blue and white in the bottom row.
+    fn test_image() -> Arc<Canvas> {
+        let mut image = Canvas::new(2, 2);
+        image.set_pixel(0, 0, Color::red()).unwrap();
+        image.set_pixel(1, 0, Color::green()).unwrap();
+        image.set_pixel(0, 1, Color::blue()).unwrap();
+        image.set_pixel(1, 1, Color::white()).unwrap();
+        Arc::new(image)
+    }
+
+    macro_rules! assert_color_approx_eq {
+        ($color1:expr, $color2:expr) => {
+            assert!(approx_eq!($color1.r, $color2.r));
+            assert!(approx_eq!($color1.g, $color2.g));
+            assert!(approx_eq!($color1.b, $color2.b));
+        };
+    }
+
+    #[test]
+    fn checker_texture() {
+        let texture = CheckerTexture {
+            width: 2,
+            height: 2,
+            a: Color::black(),
+            b: Color::white(),
+        };
+        for (u, v, color) in [
+            (0.0, 0.0, Color::black()),
+            (0.5, 0.0, Color::white()),
+            (0.0, 0.5, Color::white()),
+            (0.5, 0.5, Color::black()),
+            (1.0, 1.0, Color::black()),
+        ] {
+            assert_eq!(texture.texture_at(u, v), color);
+        }
+    }
+
+    #[test]
+    fn nearest_filter_returns_pixel_colors() {
+        let texture = ImageTexture {
+            filter: TextureFilter::Nearest,
+            ..ImageTexture::new(test_image())
+        };
+        assert_eq!(texture.texture_at(0.25, 0.75), Color::red());
+        assert_eq!(texture.texture_at(0.75, 0.75), Color::green());
+        assert_eq!(texture.texture_at(0.1, 0.1), Color::blue());
+        assert_eq!(texture.texture_at(0.9, 0.4), Color::white());
+    }
+
+    #[test]
+    fn bilinear_filter_interpolates_between_pixel_centers() {
+        let texture = ImageTexture {
+            wrap: TextureWrap::Clamp,
+            ..ImageTexture::new(test_image())
+        };
+        // Pixel centers keep their color.
+        assert_color_approx_eq!(texture.texture_at(0.25, 0.75), Color::red());
+        assert_color_approx_eq!(texture.texture_at(0.75, 0.25), Color::white());
+        // Halfway between the top pixels.
+        assert_color_approx_eq!(
+            texture.texture_at(0.5, 0.75),
+            Color {
+                r: 0.5,
+                g: 0.5,
+                b: 0.0,
+            }
+        );
+        // Center of the image.
+        assert_color_approx_eq!(
+            texture.texture_at(0.5, 0.5),
+            Color {
+                r: 0.5,
+                g: 0.5,
+                b: 0.5,
+            }
+        );
+    }
+
+    #[test]
+    fn wrap_repeats_image() {
+        let texture = ImageTexture {
+            filter: TextureFilter::Nearest,
+            wrap: TextureWrap::Wrap,
+            ..ImageTexture::new(test_image())
+        };
+        assert_eq!(texture.texture_at(1.25, 0.75), Color::red());
+        assert_eq!(texture.texture_at(-0.25, 0.75), Color::green());
+        assert_eq!(texture.texture_at(0.25, -0.25), Color::red());
+    }
+
+    #[test]
+    fn clamp_extends_border_pixels() {
+        let texture = ImageTexture {
+            filter: TextureFilter::Nearest,
+            wrap: TextureWrap::Clamp,
+            ..ImageTexture::new(test_image())
+        };
+        assert_eq!(texture.texture_at(1.25, 0.75), Color::green());
+        assert_eq!(texture.texture_at(-0.25, 0.75), Color::red());
+        assert_eq!(texture.texture_at(0.25, -0.25), Color::blue());
+        assert_eq!(texture.texture_at(1.0, 1.0), Color::green());
+    }
+
+    #[test]
+    fn bilinear_filter_wraps_across_border() {
+        let texture = ImageTexture::new(test_image());
+        // Halfway between the right and the left column of the top row.
+        assert_color_approx_eq!(
+            texture.texture_at(1.0, 0.75),
+            Color {
+                r: 0.5,
+                g: 0.5,
+                b: 0.0,
+            }
+        );
+    }
+
+    #[test]
+    fn srgb_image_is_decoded_on_load() {
+        let mut image = Canvas::new(1, 1);
+        let gray = Color {
+            r: 0.5,
+            g: 0.5,
+            b: 0.5,
+        };
+        image.set_pixel(0, 0, gray).unwrap();
+        let texture = ImageTexture::decoded(&image, TransferFunction::Srgb);
+        let color = texture.texture_at(0.5, 0.5);
+        assert!(approx_eq!(color.r, 0.21404, atol = 1e-5));
+        assert_eq!(
+            ImageTexture::decoded(&image, TransferFunction::Linear).texture_at(0.5, 0.5),
+            gray
+        );
+    }
+
+    #[test]
+    fn empty_image_is_black() {
+        let texture = ImageTexture::new(Arc::new(Canvas::new(0, 0)));
+        assert_eq!(texture.texture_at(0.5, 0.5), Color::black());
+    }
+}
@@ -0,0 +1,203 @@
+use crate::{Float, Point};
+use std::f64::consts::PI;
+
+/// Projection of points in object space onto texture coordinates (u, v) in [0, 1],
+/// with v = 0 at the bottom of the texture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UvMapping {
+    /// Longitude and latitude on a sphere around the origin.
+    Spherical,
+    /// The xz plane, repeating every unit in x and z.
+    Planar,
+    /// Angle around the y axis and height, repeating every unit in y.
+    Cylindrical,
+    /// Faces of the cube from -1 to 1, laid out as a horizontal cross:
+    /// the middle row holds left, front, right and back, with up above and
+    /// down below the front face.
+    Cube,
+    /// Texture coordinates stored on the surface, like the `vt` coordinates of OBJ
+    /// files. Points without them are mapped like `Planar`.
+    Surface,
+}
+
+impl UvMapping {
+    /// Texture coordinates of a point, ignoring the ones stored on the surface.
+    pub fn map(&self, point: Point) -> (Float, Float) {
+        match self {
+            UvMapping::Spherical => spherical_map(point),
+            UvMapping::Planar | UvMapping::Surface => {
+                (point.x.rem_euclid(1.0), point.z.rem_euclid(1.0))
+            }
+            UvMapping::Cylindrical => (angle_around_y(point), point.y.rem_euclid(1.0)),
+            UvMapping::Cube => cube_map(point),
+        }
+    }
+
+    /// Like `map`, but using the texture coordinates stored on the surface with
+    /// the `Surface` mapping.
+    pub fn map_surface(
+        &self,
+        point: Point,
+        texture_coordinates: Option<(Float, Float)>,
+    ) -> (Float, Float) {
+        match (self, texture_coordinates) {
+            (UvMapping::Surface, Some(uv)) => uv,
+            _ => self.map(point),
+        }
+    }
+}
+
+// Fraction of a full turn around the y axis, starting at -z and going over +x.
+fn angle_around_y(point: Point) -> Float {
+    let theta = Float::atan2(point.x, point.z);
+    1.0 - (theta / (2.0 * PI as Float) + 0.5)
+}
+
+fn spherical_map(point: Point) -> (Float, Float) {
+    let radius = (point - Point::origin()).length();
+    let phi = Float::acos(point.y / radius);
+    (angle_around_y(point), 1.0 - phi / PI as Float)
+}
+
+fn cube_map(point: Point) -> (Float, Float) {
+    let face = |value: Float| ((value + 1.0) / 2.0).clamp(0.0, 1.0);
+    let Point { x, y, z } = point;
+    // Column and row of the face in the cross, and the coordinates on the face.
+    let max = x.abs().max(y.abs()).max(z.abs());
+    let (column, row, u, v) = if max == x {
+        (2.0, 1.0, face(-z), face(y))
+    } else if max == -x {
+        (0.0, 1.0, face(z), face(y))
+    } else if max == y {
+        (1.0, 2.0, face(x), face(-z))
+    } else if max == -y {
+        (1.0, 0.0, face(x), face(z))
+    } else if max == z {
+        (1.0, 1.0, face(x), face(y))
+    } else {
+        (3.0, 1.0, face(-x), face(y))
+    };
+    ((column + u) / 4.0, (row + v) / 3.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::approx_eq;
+
+    fn assert_maps_to(mapping: UvMapping, (x, y, z): (Float, Float, Float), uv: (Float, Float)) {
+        let (u, v) = mapping.map(Point { x, y, z });
+        assert!(
+            approx_eq!(u, uv.0, atol = 1e-5),
+            "u = {u} for {x}, {y}, {z}"
+        );
+        assert!(
+            approx_eq!(v, uv.1, atol = 1e-5),
+            "v = {v} for {x}, {y}, {z}"
+        );
+    }
+
+    #[test]
+    fn spherical_mapping() {
+        let value = std::f64::consts::FRAC_1_SQRT_2 as Float;
+        for (point, uv) in [
+            ((0.0, 0.0, -1.0), (0.0, 0.5)),
+            ((1.0, 0.0, 0.0), (0.25, 0.5)),
+            ((0.0, 0.0, 1.0), (0.5, 0.5)),
+            ((-1.0, 0.0, 0.0), (0.75, 0.5)),
+            ((0.0, 1.0, 0.0), (0.5, 1.0)),
+            ((0.0, -1.0, 0.0), (0.5, 0.0)),
+            ((value, value, 0.0), (0.25, 0.75)),
+        ] {
+            assert_maps_to(UvMapping::Spherical, point, uv);
+        }
+    }
+
+    #[test]
+    fn planar_mapping() {
+        for (point, uv) in [
+            ((0.25, 0.0, 0.5), (0.25, 0.5)),
+            ((0.25, 0.0, -0.25), (0.25, 0.75)),
+            ((0.25, 0.5, -0.25), (0.25, 0.75)),
+            ((1.25, 0.0, 0.5), (0.25, 0.5)),
+            ((0.25, 0.0, -1.75), (0.25, 0.25)),
+            ((1.0, 0.0, -1.0), (0.0, 0.0)),
+            ((0.0, 0.0, 0.0), (0.0, 0.0)),
+        ] {
+            assert_maps_to(UvMapping::Planar, point, uv);
+        }
+    }
+
+    #[test]
+    fn cylindrical_mapping() {
+        let value = std::f64::consts::FRAC_1_SQRT_2 as Float;
+        for (point, uv) in [
+            ((0.0, 0.0, -1.0), (0.0, 0.0)),
+            ((0.0, 0.5, -1.0), (0.0, 0.5)),
+            ((0.0, 1.0, -1.0), (0.0, 0.0)),
+            ((value, 0.5, -value), (0.125, 0.5)),
+            ((1.0, 0.5, 0.0), (0.25, 0.5)),
+            ((value, 0.5, value), (0.375, 0.5)),
+            ((0.0, -0.25, 1.0), (0.5, 0.75)),
+            ((-value, 0.5, value), (0.625, 0.5)),
+            ((-1.0, 1.25, 0.0), (0.75, 0.25)),
+            ((-value, 0.5, -value), (0.875, 0.5)),
+        ] {
+            assert_maps_to(UvMapping::Cylindrical, point, uv);
+        }
+    }
+
+    #[test]
+    fn cube_mapping_places_faces_in_cross() {
+        // Face centers.
+        for (point, uv) in [
+            ((-1.0, 0.0, 0.0), (0.125, 0.5)),
+            ((0.0, 0.0, 1.0), (0.375, 0.5)),
+            ((1.0, 0.0, 0.0), (0.625, 0.5)),
+            ((0.0, 0.0, -1.0), (0.875, 0.5)),
+            ((0.0, 1.0, 0.0), (0.375, 5.0 / 6.0)),
+            ((0.0, -1.0, 0.0), (0.375, 1.0 / 6.0)),
+        ] {
+            assert_maps_to(UvMapping::Cube, point, uv);
+        }
+    }
+
+    #[test]
+    fn cube_mapping_on_faces() {
+        for (point, uv) in [
+            // Front, upper left and lower right.
+            ((-0.5, 0.5, 1.0), (0.3125, 1.75 / 3.0)),
+            ((0.5, -0.5, 1.0), (0.4375, 1.25 / 3.0)),
+            // Right, upper left.
+            ((1.0, 0.5, 0.5), (0.5625, 1.75 / 3.0)),
+            // Back, upper left.
+            ((0.5, 0.5, -1.0), (0.8125, 1.75 / 3.0)),
+            // Up, the edge towards the front is at the bottom.
+            ((-0.5, 1.0, 0.5), (0.3125, 2.25 / 3.0)),
+            // Down, the edge towards the front is at the top.
+            ((-0.5, -1.0, 0.5), (0.3125, 0.75 / 3.0)),
+        ] {
+            assert_maps_to(UvMapping::Cube, point, uv);
+        }
+    }
+
+    #[test]
+    fn cube_mapping_on_edges_and_corners() {
+        for (point, uv) in [
+            // Edges of the right face, towards up and back.
+            ((1.0, 1.0, 0.0), (0.625, 2.0 / 3.0)),
+            ((1.0, 0.0, -1.0), (0.75, 0.5)),
+            // Edge of the front face towards the right.
+            ((1.0, 0.0, 1.0), (0.5, 0.5)),
+            // Edge of the up face towards the back.
+            ((0.0, 1.0, -1.0), (0.375, 1.0)),
+            // Corners, assigned to the x faces.
+            ((1.0, 1.0, 1.0), (0.5, 2.0 / 3.0)),
+            ((1.0, -1.0, -1.0), (0.75, 1.0 / 3.0)),
+            ((-1.0, 1.0, -1.0), (0.0, 2.0 / 3.0)),
+            ((-1.0, -1.0, 1.0), (0.25, 1.0 / 3.0)),
+        ] {
+            assert_maps_to(UvMapping::Cube, point, uv);
+        }
+    }
+}
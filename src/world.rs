@@ -0,0 +1,893 @@
+use crate::{
+    get_hit, BoundingBox, Bvh, Color, Computations, Float, Intersection, Point, PointLight, Ray,
+    Shape,
+};
+use std::sync::OnceLock;
+
+#[derive(Debug)]
+pub struct World {
+    objects: Vec<Box<dyn Shape>>,
+    pub lights: Vec<PointLight>,
+    /// Maximum number of reflections and refractions followed per ray, so that rays bouncing
+    /// between two mirrors terminate.
+    pub max_depth: usize,
+    /// Built over the objects on the first intersection.
+    bvh: OnceLock<Bvh>,
+}
+
+impl Default for World {
+    fn default() -> Self {
+        World {
+            objects: vec![],
+            lights: vec![],
+            max_depth: 5,
+            bvh: OnceLock::new(),
+        }
+    }
+}
+
+impl World {
+    pub fn new(objects: Vec<Box<dyn Shape>>, lights: Vec<PointLight>) -> Self {
+        World {
+            objects,
+            lights,
+            ..Default::default()
+        }
+    }
+
+    pub fn objects(&self) -> &[Box<dyn Shape>] {
+        &self.objects
+    }
+
+    /// Gives mutable access to the objects, the bounding volume hierarchy is
+    /// rebuilt on the next intersection.
+    pub fn objects_mut(&mut self) -> &mut Vec<Box<dyn Shape>> {
+        self.bvh.take();
+        &mut self.objects
+    }
+
+    /// Returns all intersections of the ray with objects in the world, sorted by `t`.
+    pub fn intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        let bvh = self.bvh.get_or_init(|| {
+            let bounds: Vec<BoundingBox> =
+                self.objects.iter().map(|object| object.bounds()).collect();
+            Bvh::new(&bounds)
+        });
+        let mut intersections = vec![];
+        bvh.traverse(ray, |i| {
+            intersections.extend(ray.intersect(self.objects[i].as_ref()))
+        });
+        intersections.sort_by(|a, b| a.t.total_cmp(&b.t));
+        intersections
+    }
+
+    /// Color at the intersection, following at most `remaining` reflections or refractions.
+    pub fn shade_hit(&self, comps: &Computations, remaining: usize) -> Color {
+        let material = comps.material;
+        let surface = self.lights.iter().fold(Color::black(), |color, light| {
+            let in_shadow = self.is_shadowed(light, comps.over_point);
+            color + material.lighting(*light, comps, in_shadow)
+        });
+        let reflected = self.reflected_color(comps, remaining);
+        let refracted = self.refracted_color(comps, remaining);
+        if material.reflective > 0.0 && material.transparency > 0.0 {
+            let reflectance = comps.schlick();
+            surface + reflected * reflectance + refracted * (1.0 - reflectance)
+        } else {
+            surface + reflected + refracted
+        }
+    }
+
+    pub fn reflected_color(&self, comps: &Computations, remaining: usize) -> Color {
+        let reflective = comps.material.reflective;
+        if remaining == 0 || reflective == 0.0 {
+            return Color::black();
+        }
+        let ray = Ray {
+            origin: comps.over_point,
+            direction: comps.reflectv,
+        };
+        self.color_at_depth(&ray, remaining - 1) * reflective
+    }
+
+    pub fn refracted_color(&self, comps: &Computations, remaining: usize) -> Color {
+        let transparency = comps.material.transparency;
+        if remaining == 0 || transparency == 0.0 {
+            return Color::black();
+        }
+        // Snell's law, with the angles given by their sine and cosine.
+        let n_ratio = comps.n1 / comps.n2;
+        let cos_i = comps.eye.dot(comps.normal);
+        let sin2_t = n_ratio * n_ratio * (1.0 - cos_i * cos_i);
+        // Total internal reflection, the light is reflected instead.
+        if sin2_t > 1.0 {
+            return Color::black();
+        }
+        let cos_t = Float::sqrt(1.0 - sin2_t);
+        let ray = Ray {
+            origin: comps.under_point,
+            direction: comps.normal * (n_ratio * cos_i - cos_t) - comps.eye * n_ratio,
+        };
+        self.color_at_depth(&ray, remaining - 1) * transparency
+    }
+
+    /// Checks whether any object lies between the point and the light.
+    pub fn is_shadowed(&self, light: &PointLight, point: Point) -> bool {
+        let to_light = light.position - point;
+        let distance = to_light.length();
+        let ray = Ray {
+            origin: point,
+            direction: to_light.normalize(),
+        };
+        match get_hit(&self.intersect(&ray)) {
+            Some(hit) => hit.t < distance,
+            None => false,
+        }
+    }
+
+    pub fn color_at(&self, ray: &Ray) -> Color {
+        self.color_at_depth(ray, self.max_depth)
+    }
+
+    /// Like `color_at`, but following at most `remaining` reflections or refractions.
+    pub fn color_at_depth(&self, ray: &Ray, remaining: usize) -> Color {
+        let intersections = self.intersect(ray);
+        match get_hit(&intersections) {
+            Some(hit) => self.shade_hit(&hit.prepare_computations(ray, &intersections), remaining),
+            None => Color::black(),
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::patterns::tests::TestPattern;
+    use crate::{approx_eq, Float, Material, Matrix4x4, Plane, Sphere, Transform, Vector};
+    use std::sync::Arc;
+
+    // Reference values are only given to 5 decimal places.
+    macro_rules! assert_color_approx_eq {
+        ($color1:expr, $color2:expr) => {
+            assert!(approx_eq!($color1.r, $color2.r, atol = 1e-5));
+            assert!(approx_eq!($color1.g, $color2.g, atol = 1e-5));
+            assert!(approx_eq!($color1.b, $color2.b, atol = 1e-5));
+        };
+    }
+
+    pub(crate) fn default_world() -> World {
+        let outer = Sphere {
+            material: Material {
+                color: Color {
+                    r: 0.8,
+                    g: 1.0,
+                    b: 0.6,
+                },
+                diffuse: 0.7,
+                specular: 0.2,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let inner = Sphere {
+            transform: Transform::new(Matrix4x4::scaling(0.5, 0.5, 0.5)).unwrap(),
+            ..Default::default()
+        };
+        World::new(
+            vec![Box::new(outer), Box::new(inner)],
+            vec![PointLight {
+                position: Point {
+                    x: -10.0,
+                    y: 10.0,
+                    z: -10.0,
+                },
+                intensity: Color::white(),
+            }],
+        )
+    }
+
+    // Row of small spheres along the x axis, above a floor.
+    fn sphere_row_world() -> World {
+        let mut objects: Vec<Box<dyn Shape>> = (0..20)
+            .map(|i| -> Box<dyn Shape> {
+                Box::new(Sphere {
+                    transform: Transform::new(
+                        Matrix4x4::translation(i as Float * 3.0, 0.0, 0.0)
+                            .matmul(Matrix4x4::scaling(0.5, 0.5, 0.5)),
+                    )
+                    .unwrap(),
+                    ..Default::default()
+                })
+            })
+            .collect();
+        objects.push(Box::new(Plane {
+            transform: Transform::new(Matrix4x4::translation(0.0, -1.0, 0.0)).unwrap(),
+            ..Default::default()
+        }));
+        World::new(objects, vec![])
+    }
+
+    #[test]
+    fn intersect_finds_nearest_hit_through_bvh() {
+        let world = sphere_row_world();
+        let ray = Ray {
+            origin: Point {
+                x: 21.0,
+                y: 0.0,
+                z: -5.0,
+            },
+            direction: Vector {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0,
+            },
+        };
+        let intersections = world.intersect(&ray);
+        let hit = get_hit(&intersections).unwrap();
+        assert_eq!(hit.object, world.objects()[7].as_ref());
+        assert!(approx_eq!(hit.t, 4.5));
+        // Only the sphere in front of the ray is hit.
+        assert_eq!(intersections.len(), 2);
+        // The floor is outside the hierarchy, but still found.
+        let ray = Ray {
+            origin: Point {
+                x: 1.5,
+                y: 1.0,
+                z: 0.0,
+            },
+            direction: Vector {
+                x: 0.0,
+                y: -1.0,
+                z: 0.0,
+            },
+        };
+        let intersections = world.intersect(&ray);
+        let hit = get_hit(&intersections).unwrap();
+        assert_eq!(hit.object, world.objects()[20].as_ref());
+        assert!(approx_eq!(hit.t, 2.0));
+    }
+
+    #[test]
+    fn intersect_after_changing_objects() {
+        let mut world = sphere_row_world();
+        let ray = Ray {
+            origin: Point {
+                x: -3.0,
+                y: 0.0,
+                z: -5.0,
+            },
+            direction: Vector {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0,
+            },
+        };
+        assert!(world.intersect(&ray).is_empty());
+        world.objects_mut().push(Box::new(Sphere {
+            transform: Transform::new(Matrix4x4::translation(-3.0, 0.0, 0.0)).unwrap(),
+            ..Default::default()
+        }));
+        let intersections = world.intersect(&ray);
+        assert_eq!(intersections.len(), 2);
+        assert_eq!(intersections[0].object, world.objects()[21].as_ref());
+        // Removing objects must not leave stale indices behind.
+        world.objects_mut().truncate(1);
+        assert!(world.intersect(&ray).is_empty());
+    }
+
+    #[test]
+    fn create_empty_world() {
+        let world = World::default();
+        assert!(world.objects.is_empty());
+        assert!(world.lights.is_empty());
+        assert_eq!(world.max_depth, 5);
+    }
+
+    #[test]
+    fn intersect_world_with_ray() {
+        let world = default_world();
+        let ray = Ray {
+            origin: Point {
+                x: 0.0,
+                y: 0.0,
+                z: -5.0,
+            },
+            direction: Vector {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0,
+            },
+        };
+        let intersections = world.intersect(&ray);
+        let ts: Vec<Float> = intersections.iter().map(|i| i.t).collect();
+        assert_eq!(ts, vec![4.0, 4.5, 5.5, 6.0]);
+    }
+
+    #[test]
+    fn shade_intersection() {
+        let world = default_world();
+        let ray = Ray {
+            origin: Point {
+                x: 0.0,
+                y: 0.0,
+                z: -5.0,
+            },
+            direction: Vector {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0,
+            },
+        };
+        let intersections = [Intersection::new(4.0, world.objects[0].as_ref())];
+        let comps = intersections[0].prepare_computations(&ray, &intersections);
+        assert_color_approx_eq!(
+            world.shade_hit(&comps, world.max_depth),
+            Color {
+                r: 0.38066,
+                g: 0.47583,
+                b: 0.2855,
+            }
+        );
+    }
+
+    #[test]
+    fn shade_intersection_from_inside() {
+        let mut world = default_world();
+        world.lights = vec![PointLight {
+            position: Point {
+                x: 0.0,
+                y: 0.25,
+                z: 0.0,
+            },
+            intensity: Color::white(),
+        }];
+        let ray = Ray {
+            origin: Point::origin(),
+            direction: Vector {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0,
+            },
+        };
+        let intersections = [Intersection::new(0.5, world.objects[1].as_ref())];
+        let comps = intersections[0].prepare_computations(&ray, &intersections);
+        assert_color_approx_eq!(
+            world.shade_hit(&comps, world.max_depth),
+            Color {
+                r: 0.90498,
+                g: 0.90498,
+                b: 0.90498,
+            }
+        );
+    }
+
+    #[test]
+    fn color_when_ray_misses() {
+        let world = default_world();
+        let ray = Ray {
+            origin: Point {
+                x: 0.0,
+                y: 0.0,
+                z: -5.0,
+            },
+            direction: Vector {
+                x: 0.0,
+                y: 1.0,
+                z: 0.0,
+            },
+        };
+        assert_eq!(world.color_at(&ray), Color::black());
+    }
+
+    #[test]
+    fn color_when_ray_hits() {
+        let world = default_world();
+        let ray = Ray {
+            origin: Point {
+                x: 0.0,
+                y: 0.0,
+                z: -5.0,
+            },
+            direction: Vector {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0,
+            },
+        };
+        assert_color_approx_eq!(
+            world.color_at(&ray),
+            Color {
+                r: 0.38066,
+                g: 0.47583,
+                b: 0.2855,
+            }
+        );
+    }
+
+    #[test]
+    fn color_with_intersection_behind_ray() {
+        let outer = Sphere {
+            material: Material {
+                ambient: 1.0,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let inner = Sphere {
+            transform: Transform::new(Matrix4x4::scaling(0.5, 0.5, 0.5)).unwrap(),
+            material: Material {
+                color: Color::red(),
+                ambient: 1.0,
+                ..Default::default()
+            },
+        };
+        let world = World {
+            objects: vec![Box::new(outer), Box::new(inner)],
+            ..default_world()
+        };
+        let ray = Ray {
+            origin: Point {
+                x: 0.0,
+                y: 0.0,
+                z: 0.75,
+            },
+            direction: Vector {
+                x: 0.0,
+                y: 0.0,
+                z: -1.0,
+            },
+        };
+        assert_color_approx_eq!(world.color_at(&ray), Color::red());
+    }
+
+    #[test]
+    fn light_contributions_are_summed() {
+        let mut world = default_world();
+        let light = world.lights[0];
+        world.lights.push(light);
+        let ray = Ray {
+            origin: Point {
+                x: 0.0,
+                y: 0.0,
+                z: -5.0,
+            },
+            direction: Vector {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0,
+            },
+        };
+        assert_color_approx_eq!(
+            world.color_at(&ray),
+            Color {
+                r: 0.38066 * 2.0,
+                g: 0.47583 * 2.0,
+                b: 0.2855 * 2.0,
+            }
+        );
+    }
+
+    #[test]
+    fn no_shadow_when_nothing_is_collinear_with_point_and_light() {
+        let world = default_world();
+        let point = Point {
+            x: 0.0,
+            y: 10.0,
+            z: 0.0,
+        };
+        assert!(!world.is_shadowed(&world.lights[0], point));
+    }
+
+    #[test]
+    fn shadow_when_object_is_between_point_and_light() {
+        let world = default_world();
+        let point = Point {
+            x: 10.0,
+            y: -10.0,
+            z: 10.0,
+        };
+        assert!(world.is_shadowed(&world.lights[0], point));
+    }
+
+    #[test]
+    fn no_shadow_when_object_is_behind_light() {
+        let world = default_world();
+        let point = Point {
+            x: -20.0,
+            y: 20.0,
+            z: -20.0,
+        };
+        assert!(!world.is_shadowed(&world.lights[0], point));
+    }
+
+    #[test]
+    fn no_shadow_when_object_is_behind_point() {
+        let world = default_world();
+        let point = Point {
+            x: -2.0,
+            y: 2.0,
+            z: -2.0,
+        };
+        assert!(!world.is_shadowed(&world.lights[0], point));
+    }
+
+    #[test]
+    fn shade_intersection_in_shadow() {
+        let first = Sphere::default();
+        let second = Sphere {
+            transform: Transform::new(Matrix4x4::translation(0.0, 0.0, 10.0)).unwrap(),
+            ..Default::default()
+        };
+        let world = World::new(
+            vec![Box::new(first), Box::new(second)],
+            vec![PointLight {
+                position: Point {
+                    x: 0.0,
+                    y: 0.0,
+                    z: -10.0,
+                },
+                intensity: Color::white(),
+            }],
+        );
+        let ray = Ray {
+            origin: Point {
+                x: 0.0,
+                y: 0.0,
+                z: 5.0,
+            },
+            direction: Vector {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0,
+            },
+        };
+        let intersections = [Intersection::new(4.0, world.objects[1].as_ref())];
+        let comps = intersections[0].prepare_computations(&ray, &intersections);
+        assert_color_approx_eq!(
+            world.shade_hit(&comps, world.max_depth),
+            Color {
+                r: 0.1,
+                g: 0.1,
+                b: 0.1,
+            }
+        );
+    }
+
+    // Reflective plane below the default world, hit at its origin by `ray`.
+    fn world_with_reflective_plane() -> (World, Ray) {
+        let mut world = default_world();
+        world.objects_mut().push(Box::new(Plane {
+            transform: Transform::new(Matrix4x4::translation(0.0, -1.0, 0.0)).unwrap(),
+            material: Material {
+                reflective: 0.5,
+                ..Default::default()
+            },
+        }));
+        let value = std::f64::consts::FRAC_1_SQRT_2 as Float;
+        let ray = Ray {
+            origin: Point {
+                x: 0.0,
+                y: 0.0,
+                z: -3.0,
+            },
+            direction: Vector {
+                x: 0.0,
+                y: -value,
+                z: value,
+            },
+        };
+        (world, ray)
+    }
+
+    #[test]
+    fn reflected_color_for_nonreflective_material() {
+        let world = default_world();
+        let ray = Ray {
+            origin: Point::origin(),
+            direction: Vector {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0,
+            },
+        };
+        // The inner sphere, with ambient set to 1 so that only reflections could differ.
+        let inner = Sphere {
+            material: Material {
+                ambient: 1.0,
+                ..world.objects[1].material().clone()
+            },
+            ..Default::default()
+        };
+        let intersections = [Intersection::new(1.0, &inner)];
+        let comps = intersections[0].prepare_computations(&ray, &intersections);
+        assert_eq!(
+            world.reflected_color(&comps, world.max_depth),
+            Color::black()
+        );
+    }
+
+    #[test]
+    fn reflected_color_for_reflective_material() {
+        let (world, ray) = world_with_reflective_plane();
+        let intersections = [Intersection::new(
+            Float::sqrt(2.0),
+            world.objects[2].as_ref(),
+        )];
+        let comps = intersections[0].prepare_computations(&ray, &intersections);
+        assert_color_approx_eq!(
+            world.reflected_color(&comps, world.max_depth),
+            Color {
+                r: 0.19033,
+                g: 0.23791,
+                b: 0.14274,
+            }
+        );
+    }
+
+    #[test]
+    fn shade_hit_with_reflective_material() {
+        let (world, ray) = world_with_reflective_plane();
+        let intersections = [Intersection::new(
+            Float::sqrt(2.0),
+            world.objects[2].as_ref(),
+        )];
+        let comps = intersections[0].prepare_computations(&ray, &intersections);
+        assert_color_approx_eq!(
+            world.shade_hit(&comps, world.max_depth),
+            Color {
+                r: 0.87675,
+                g: 0.92434,
+                b: 0.82917,
+            }
+        );
+    }
+
+    #[test]
+    fn reflected_color_at_maximum_recursion_depth() {
+        let (world, ray) = world_with_reflective_plane();
+        let intersections = [Intersection::new(
+            Float::sqrt(2.0),
+            world.objects[2].as_ref(),
+        )];
+        let comps = intersections[0].prepare_computations(&ray, &intersections);
+        assert_eq!(world.reflected_color(&comps, 0), Color::black());
+    }
+
+    #[test]
+    fn mutually_reflective_surfaces_terminate() {
+        let mirror = Material {
+            reflective: 1.0,
+            ..Default::default()
+        };
+        let world = World {
+            objects: vec![
+                Box::new(Plane {
+                    transform: Transform::new(Matrix4x4::translation(0.0, -1.0, 0.0)).unwrap(),
+                    material: mirror.clone(),
+                }),
+                Box::new(Plane {
+                    transform: Transform::new(Matrix4x4::translation(0.0, 1.0, 0.0)).unwrap(),
+                    material: mirror,
+                }),
+            ],
+            lights: vec![PointLight {
+                position: Point::origin(),
+                intensity: Color::white(),
+            }],
+            max_depth: 100,
+            ..Default::default()
+        };
+        let ray = Ray {
+            origin: Point::origin(),
+            direction: Vector {
+                x: 0.0,
+                y: 1.0,
+                z: 0.0,
+            },
+        };
+        // Every bounce adds the same amount of light, up to the maximum depth.
+        let color = world.color_at(&ray);
+        assert!(color.r.is_finite() && color.r > 1.0);
+    }
+
+    // Default world with its outer sphere replaced by a glass sphere.
+    fn world_with_glass_sphere() -> World {
+        let mut world = default_world();
+        world.objects_mut()[0] = Box::new(Sphere {
+            material: Material {
+                transparency: 1.0,
+                refractive_index: 1.5,
+                ..world.objects[0].material().clone()
+            },
+            ..Default::default()
+        });
+        world
+    }
+
+    #[test]
+    fn refracted_color_for_opaque_material() {
+        let world = default_world();
+        let ray = Ray {
+            origin: Point {
+                x: 0.0,
+                y: 0.0,
+                z: -5.0,
+            },
+            direction: Vector {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0,
+            },
+        };
+        let shape = world.objects[0].as_ref();
+        let intersections = [Intersection::new(4.0, shape), Intersection::new(6.0, shape)];
+        let comps = intersections[0].prepare_computations(&ray, &intersections);
+        assert_eq!(world.refracted_color(&comps, 5), Color::black());
+    }
+
+    #[test]
+    fn refracted_color_at_maximum_recursion_depth() {
+        let world = world_with_glass_sphere();
+        let ray = Ray {
+            origin: Point {
+                x: 0.0,
+                y: 0.0,
+                z: -5.0,
+            },
+            direction: Vector {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0,
+            },
+        };
+        let shape = world.objects[0].as_ref();
+        let intersections = [Intersection::new(4.0, shape), Intersection::new(6.0, shape)];
+        let comps = intersections[0].prepare_computations(&ray, &intersections);
+        assert_eq!(world.refracted_color(&comps, 0), Color::black());
+    }
+
+    #[test]
+    fn refracted_color_under_total_internal_reflection() {
+        let world = world_with_glass_sphere();
+        let value = std::f64::consts::FRAC_1_SQRT_2 as Float;
+        let ray = Ray {
+            origin: Point {
+                x: 0.0,
+                y: 0.0,
+                z: value,
+            },
+            direction: Vector {
+                x: 0.0,
+                y: 1.0,
+                z: 0.0,
+            },
+        };
+        let shape = world.objects[0].as_ref();
+        let intersections = [
+            Intersection::new(-value, shape),
+            Intersection::new(value, shape),
+        ];
+        // The ray starts inside the sphere, so the second intersection is the relevant one.
+        let comps = intersections[1].prepare_computations(&ray, &intersections);
+        assert_eq!(world.refracted_color(&comps, 5), Color::black());
+    }
+
+    #[test]
+    fn refracted_color_with_refracted_ray() {
+        let mut world = default_world();
+        // Outer sphere showing where the refracted ray hits it.
+        world.objects[0] = Box::new(Sphere {
+            material: Material {
+                ambient: 1.0,
+                pattern: Some(Arc::new(TestPattern::default())),
+                ..world.objects[0].material().clone()
+            },
+            ..Default::default()
+        });
+        world.objects[1] = Box::new(Sphere {
+            transform: *world.objects[1].transform(),
+            material: Material {
+                transparency: 1.0,
+                refractive_index: 1.5,
+                ..world.objects[1].material().clone()
+            },
+        });
+        let ray = Ray {
+            origin: Point {
+                x: 0.0,
+                y: 0.0,
+                z: 0.1,
+            },
+            direction: Vector {
+                x: 0.0,
+                y: 1.0,
+                z: 0.0,
+            },
+        };
+        let (a, b) = (world.objects[0].as_ref(), world.objects[1].as_ref());
+        let intersections = [
+            Intersection::new(-0.9899, a),
+            Intersection::new(-0.4899, b),
+            Intersection::new(0.4899, b),
+            Intersection::new(0.9899, a),
+        ];
+        let comps = intersections[2].prepare_computations(&ray, &intersections);
+        let color = world.refracted_color(&comps, 5);
+        // The reference value for blue assumes a larger offset for `under_point`.
+        assert!(approx_eq!(color.r, 0.0, atol = 1e-5));
+        assert!(approx_eq!(color.g, 0.99888, atol = 1e-5));
+        assert!(approx_eq!(color.b, 0.04725, atol = 1e-4));
+    }
+
+    // Default world with a transparent floor and a red ball below it.
+    fn world_with_transparent_floor(reflective: Float) -> (World, Ray) {
+        let mut world = default_world();
+        world.objects_mut().push(Box::new(Plane {
+            transform: Transform::new(Matrix4x4::translation(0.0, -1.0, 0.0)).unwrap(),
+            material: Material {
+                reflective,
+                transparency: 0.5,
+                refractive_index: 1.5,
+                ..Default::default()
+            },
+        }));
+        world.objects_mut().push(Box::new(Sphere {
+            transform: Transform::new(Matrix4x4::translation(0.0, -3.5, -0.5)).unwrap(),
+            material: Material {
+                color: Color::red(),
+                ambient: 0.5,
+                ..Default::default()
+            },
+        }));
+        let value = std::f64::consts::FRAC_1_SQRT_2 as Float;
+        let ray = Ray {
+            origin: Point {
+                x: 0.0,
+                y: 0.0,
+                z: -3.0,
+            },
+            direction: Vector {
+                x: 0.0,
+                y: -value,
+                z: value,
+            },
+        };
+        (world, ray)
+    }
+
+    #[test]
+    fn shade_hit_with_transparent_material() {
+        let (world, ray) = world_with_transparent_floor(0.0);
+        let intersections = [Intersection::new(
+            Float::sqrt(2.0),
+            world.objects[2].as_ref(),
+        )];
+        let comps = intersections[0].prepare_computations(&ray, &intersections);
+        assert_color_approx_eq!(
+            world.shade_hit(&comps, 5),
+            Color {
+                r: 0.93642,
+                g: 0.68642,
+                b: 0.68642,
+            }
+        );
+    }
+
+    #[test]
+    fn shade_hit_with_reflective_transparent_material() {
+        let (world, ray) = world_with_transparent_floor(0.5);
+        let intersections = [Intersection::new(
+            Float::sqrt(2.0),
+            world.objects[2].as_ref(),
+        )];
+        let comps = intersections[0].prepare_computations(&ray, &intersections);
+        assert_color_approx_eq!(
+            world.shade_hit(&comps, 5),
+            Color {
+                r: 0.93391,
+                g: 0.69643,
+                b: 0.69243,
+            }
+        );
+    }
+}